@@ -6,7 +6,10 @@
 //! # Structure
 //!
 //! A FerrisScript program consists of:
+//! - Modules it uses ([`UseDecl`])
+//! - Constant declarations ([`ConstDecl`])
 //! - Global variable declarations ([`GlobalVar`])
+//! - Struct declarations ([`StructDecl`]) and their `impl` blocks ([`ImplBlock`])
 //! - Function definitions ([`Function`])
 //!
 //! Functions contain:
@@ -44,14 +47,27 @@ pub use crate::span::Span;
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct Program {
+    /// Modules the script uses (`use "path";`)
+    pub uses: Vec<UseDecl>,
+    /// Constant declarations (`const`), in declaration order
+    pub consts: Vec<ConstDecl>,
     /// Global variable declarations (let and let mut)
     pub global_vars: Vec<GlobalVar>,
     /// Signal declarations
     pub signals: Vec<Signal>,
+    /// Enum declarations
+    pub enums: Vec<EnumDecl>,
+    /// Struct declarations
+    pub structs: Vec<StructDecl>,
+    /// Method blocks attached to user-declared structs
+    pub impls: Vec<ImplBlock>,
     /// Function definitions
     pub functions: Vec<Function>,
     /// Property metadata for exported variables (generated during type checking)
     pub property_metadata: Vec<PropertyMetadata>,
+    /// The script and the modules compiled into it, indexed by the `file` of
+    /// each span (filled in by the compiler)
+    pub files: Vec<SourceFile>,
 }
 
 impl Default for Program {
@@ -63,22 +79,43 @@ impl Default for Program {
 impl Program {
     pub fn new() -> Self {
         Program {
+            uses: Vec::new(),
+            consts: Vec::new(),
             global_vars: Vec::new(),
             signals: Vec::new(),
+            enums: Vec::new(),
+            structs: Vec::new(),
+            impls: Vec::new(),
             functions: Vec::new(),
             property_metadata: Vec::new(),
+            files: Vec::new(),
         }
     }
 }
 
 impl fmt::Display for Program {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for use_decl in &self.uses {
+            writeln!(f, "{}", use_decl)?;
+        }
+        for const_decl in &self.consts {
+            writeln!(f, "{}", const_decl)?;
+        }
         for var in &self.global_vars {
             writeln!(f, "{}", var)?;
         }
         for signal in &self.signals {
             writeln!(f, "{}", signal)?;
         }
+        for enum_decl in &self.enums {
+            writeln!(f, "{}", enum_decl)?;
+        }
+        for struct_decl in &self.structs {
+            writeln!(f, "{}", struct_decl)?;
+        }
+        for impl_block in &self.impls {
+            writeln!(f, "{}", impl_block)?;
+        }
         for func in &self.functions {
             writeln!(f, "{}", func)?;
         }
@@ -86,6 +123,50 @@ impl fmt::Display for Program {
     }
 }
 
+/// Module declaration (top-level only).
+///
+/// Makes the functions, constants, enums and structs of another file available
+/// under a namespace: the file name without its extension, or the name after
+/// `as`. Relative paths are resolved against the directory of the file that
+/// contains the declaration.
+///
+/// # Examples
+///
+/// ```text
+/// use "res://lib/combat.ferris";       // combat.damage(10)
+/// use "../util/math.ferris" as m;      // m.lerp(a, b, t)
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UseDecl {
+    /// Path as written
+    pub path: String,
+    /// Namespace the module's items are reached through
+    pub name: String,
+    /// Whether the namespace was given with `as`
+    pub alias: bool,
+    /// Source location
+    pub span: Span,
+}
+
+impl fmt::Display for UseDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "use \"{}\"", self.path)?;
+        if self.alias {
+            write!(f, " as {}", self.name)?;
+        }
+        write!(f, ";")
+    }
+}
+
+/// A source file of a compiled program: the script itself or a module it uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceFile {
+    /// Path the file was loaded from (empty for a script compiled from a string)
+    pub path: String,
+    /// Source text, for showing errors in context
+    pub source: String,
+}
+
 /// Property hint for exported variables.
 ///
 /// Hints provide additional metadata for how properties should be displayed
@@ -169,6 +250,37 @@ impl PropertyMetadata {
     }
 }
 
+/// Constant declaration.
+///
+/// A named compile-time constant declared at the program level. The value must
+/// fold to a literal using only literals and other constants, which may be
+/// declared before or after it; references to it are replaced by that value
+/// when the program is compiled.
+///
+/// # Examples
+///
+/// ```text
+/// const BASE_HP: i32 = 50;
+/// const MAX_HP: i32 = BASE_HP * 2;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstDecl {
+    /// Constant name
+    pub name: String,
+    /// Declared type (constants always carry an annotation)
+    pub ty: String,
+    /// Constant expression
+    pub value: Expr,
+    /// Source location
+    pub span: Span,
+}
+
+impl fmt::Display for ConstDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "const {}: {} = {};", self.name, self.ty, self.value)
+    }
+}
+
 /// Global variable declaration.
 ///
 /// Represents a variable declared at the program level (outside functions).
@@ -288,10 +400,13 @@ impl fmt::Display for Param {
 /// # Variants
 ///
 /// - `Expr` - Expression statement (e.g., function call)
-/// - `Let` - Local variable declaration
+/// - `Let` - Local variable declaration, optionally destructuring its value
 /// - `Assign` - Variable assignment
 /// - `If` - Conditional branching
 /// - `While` - Loop
+/// - `For` - Iteration over an array or integer range
+/// - `Loop` - Infinite loop, left with `break` or `return`
+/// - `Break` / `Continue` - Loop control, optionally naming a labeled loop
 /// - `Return` - Early function return
 /// - `CompoundAssign` - Combined operation and assignment (+=, -=)
 ///
@@ -299,16 +414,22 @@ impl fmt::Display for Param {
 ///
 /// ```text
 /// let x: i32 = 5;              // Let statement
+/// let (dir, dist) = nearest(); // Destructuring Let statement
 /// x = x + 1;                   // Assign statement
 /// if x > 10 { return x; }      // If + Return statements
 /// while x < 100 { x = x * 2; } // While statement
+/// for n in nums { print(n); }  // For statement
+/// 'outer: loop { break 'outer; } // Labeled Loop + Break statements
 /// x += 5;                      // CompoundAssign statement
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub enum Stmt {
     Expr(Expr),
     Let {
+        /// Empty when `pattern` destructures the value into several names
         name: String,
+        /// Names bound by a destructuring `let`
+        pattern: Option<LetPattern>,
         mutable: bool,
         ty: Option<String>,
         value: Expr,
@@ -327,10 +448,39 @@ pub enum Stmt {
         span: Span,
     },
     While {
+        /// Label such as `outer` in `'outer: while ...` (without the quote)
+        label: Option<String>,
         cond: Expr,
         body: Vec<Stmt>,
         span: Span,
     },
+    /// `for var in iterable { body }` - iterable is an array, an `Expr::Range` or
+    /// a dictionary (whose keys `var` takes)
+    For {
+        label: Option<String>,
+        var: String,
+        /// Second name of `for (key, value) in dict`, which takes each value
+        value_var: Option<String>,
+        iterable: Expr,
+        body: Vec<Stmt>,
+        span: Span,
+    },
+    /// `loop { body }` - runs until a `break` or `return`
+    Loop {
+        label: Option<String>,
+        body: Vec<Stmt>,
+        span: Span,
+    },
+    /// `break;` or `break 'label;`
+    Break {
+        label: Option<String>,
+        span: Span,
+    },
+    /// `continue;` or `continue 'label;`
+    Continue {
+        label: Option<String>,
+        span: Span,
+    },
     Return {
         value: Option<Expr>,
         span: Span,
@@ -357,6 +507,10 @@ impl Stmt {
             Stmt::Assign { span, .. } => *span,
             Stmt::If { span, .. } => *span,
             Stmt::While { span, .. } => *span,
+            Stmt::For { span, .. } => *span,
+            Stmt::Loop { span, .. } => *span,
+            Stmt::Break { span, .. } => *span,
+            Stmt::Continue { span, .. } => *span,
             Stmt::Return { span, .. } => *span,
         }
     }
@@ -396,12 +550,221 @@ impl fmt::Display for Signal {
     }
 }
 
+/// Enum declaration (top-level only).
+///
+/// Declares a set of named, payload-free variants. Variants are referenced
+/// as `EnumName.Variant` and compared with `==` or `match`.
+///
+/// # Examples
+///
+/// ```text
+/// enum Dir { Left, Right, Up, Down }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDecl {
+    /// Enum name
+    pub name: String,
+    /// Variant names in declaration order
+    pub variants: Vec<String>,
+    /// Source location
+    pub span: Span,
+}
+
+impl fmt::Display for EnumDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "enum {} {{ {} }}", self.name, self.variants.join(", "))
+    }
+}
+
+/// Struct declaration (top-level only).
+///
+/// Declares a named record type. Values are built with struct literal syntax
+/// and their fields are read and assigned with `.`.
+///
+/// # Examples
+///
+/// ```text
+/// struct Enemy { hp: i32, speed: f32 }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructDecl {
+    /// Struct name
+    pub name: String,
+    /// Fields in declaration order
+    pub fields: Vec<StructField>,
+    /// Source location
+    pub span: Span,
+}
+
+impl fmt::Display for StructDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "struct {} {{ ", self.name)?;
+        for (i, field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", field)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+/// A single `name: Type` field of a struct declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructField {
+    /// Field name
+    pub name: String,
+    pub ty: String,
+    pub span: Span,
+}
+
+impl fmt::Display for StructField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.ty)
+    }
+}
+
+/// `impl` block attaching methods to a user-declared struct.
+///
+/// # Examples
+///
+/// ```text
+/// impl Enemy {
+///     fn new(hp: i32) -> Enemy { return Enemy { hp: hp, speed: 1.0 }; }
+///     fn is_dead(&self) -> bool { return self.hp <= 0; }
+///     fn hit(&mut self, dmg: i32) { self.hp = self.hp - dmg; }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplBlock {
+    /// Name of the struct the methods belong to
+    pub type_name: String,
+    pub methods: Vec<Method>,
+    /// Source location
+    pub span: Span,
+}
+
+impl fmt::Display for ImplBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "impl {} {{", self.type_name)?;
+        for method in &self.methods {
+            write!(f, "{}", method)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Function declared inside an `impl` block.
+///
+/// Methods with a `self_param` are called on a value (`enemy.hit(5)`);
+/// those without are associated functions called on the type (`Enemy.new(10)`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+    /// Receiver, or `None` for an associated function
+    pub self_param: Option<SelfParam>,
+    /// Name, parameters (excluding the receiver), return type and body
+    pub function: Function,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(receiver) = self.self_param else {
+            return write!(f, "{}", self.function);
+        };
+        let func = &self.function;
+        write!(f, "fn {}({}", func.name, receiver)?;
+        for param in &func.params {
+            write!(f, ", {}", param)?;
+        }
+        write!(f, ")")?;
+        if let Some(ret) = &func.return_type {
+            write!(f, " -> {}", ret)?;
+        }
+        writeln!(f, " {{")?;
+        for stmt in &func.body {
+            writeln!(f, "    {}", stmt)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// How a method receives the value it is called on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfParam {
+    /// `&self` - read-only access
+    Ref,
+    /// `&mut self` - changes to `self` are written back to the receiver
+    RefMut,
+}
+
+impl fmt::Display for SelfParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelfParam::Ref => write!(f, "&self"),
+            SelfParam::RefMut => write!(f, "&mut self"),
+        }
+    }
+}
+
+/// Destructuring pattern of a `let` statement. `_` in place of a name skips
+/// that part of the value.
+///
+/// # Examples
+///
+/// ```text
+/// let (dir, dist) = nearest();         // Tuple
+/// let Vector2 { x, y: height } = pos;  // Struct
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum LetPattern {
+    /// `(a, b, ...)` - binds the elements of a tuple by position
+    Tuple(Vec<String>),
+    /// `TypeName { field, field: name, ... }` - binds fields of a Vector2, Color,
+    /// Rect2, Transform2D or struct value, each to the field's own name or to
+    /// the name after `:`
+    Struct {
+        type_name: String,
+        fields: Vec<(String, String)>,
+    },
+}
+
+impl fmt::Display for LetPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LetPattern::Tuple(names) => write!(f, "({})", names.join(", ")),
+            LetPattern::Struct { type_name, fields } => {
+                write!(f, "{} {{ ", type_name)?;
+                for (i, (field, name)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    if field == name {
+                        write!(f, "{}", field)?;
+                    } else {
+                        write!(f, "{}: {}", field, name)?;
+                    }
+                }
+                write!(f, " }}")
+            }
+        }
+    }
+}
+
+/// Write the `'label: ` prefix of a labeled loop
+fn write_label(f: &mut fmt::Formatter<'_>, label: &Option<String>) -> fmt::Result {
+    match label {
+        Some(label) => write!(f, "'{}: ", label),
+        None => Ok(()),
+    }
+}
+
 impl fmt::Display for Stmt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Stmt::Expr(expr) => write!(f, "{};", expr),
             Stmt::Let {
                 name,
+                pattern,
                 mutable,
                 ty,
                 value,
@@ -411,7 +774,10 @@ impl fmt::Display for Stmt {
                 if *mutable {
                     write!(f, "mut ")?;
                 }
-                write!(f, "{}", name)?;
+                match pattern {
+                    Some(pattern) => write!(f, "{}", pattern)?,
+                    None => write!(f, "{}", name)?,
+                }
                 if let Some(t) = ty {
                     write!(f, ": {}", t)?;
                 }
@@ -440,13 +806,52 @@ impl fmt::Display for Stmt {
                 }
                 Ok(())
             }
-            Stmt::While { cond, body, .. } => {
+            Stmt::While {
+                label, cond, body, ..
+            } => {
+                write_label(f, label)?;
                 write!(f, "while {} {{ ", cond)?;
                 for stmt in body {
                     write!(f, "{} ", stmt)?;
                 }
                 write!(f, "}}")
             }
+            Stmt::For {
+                label,
+                var,
+                value_var,
+                iterable,
+                body,
+                ..
+            } => {
+                write_label(f, label)?;
+                match value_var {
+                    Some(value_var) => {
+                        write!(f, "for ({}, {}) in {} {{ ", var, value_var, iterable)?
+                    }
+                    None => write!(f, "for {} in {} {{ ", var, iterable)?,
+                }
+                for stmt in body {
+                    write!(f, "{} ", stmt)?;
+                }
+                write!(f, "}}")
+            }
+            Stmt::Loop { label, body, .. } => {
+                write_label(f, label)?;
+                write!(f, "loop {{ ")?;
+                for stmt in body {
+                    write!(f, "{} ", stmt)?;
+                }
+                write!(f, "}}")
+            }
+            Stmt::Break { label, .. } => match label {
+                Some(label) => write!(f, "break '{};", label),
+                None => write!(f, "break;"),
+            },
+            Stmt::Continue { label, .. } => match label {
+                Some(label) => write!(f, "continue '{};", label),
+                None => write!(f, "continue;"),
+            },
             Stmt::Return { value, .. } => {
                 write!(f, "return")?;
                 if let Some(v) = value {
@@ -470,9 +875,21 @@ impl fmt::Display for Stmt {
 /// - `Binary` - Binary operation (e.g., `a + b`, `x == y`)
 /// - `Unary` - Unary operation (e.g., `-x`, `!flag`)
 /// - `Call` - Function call
-/// - `FieldAccess` - Member access (e.g., `position.x`)
+/// - `FieldAccess` - Member access (e.g., `position.x`), or a tuple element by
+///   position (e.g., `pair.0`)
 /// - `Assign` - Assignment expression
 /// - `CompoundAssign` - Combined operation (e.g., `x += 5`)
+/// - `ArrayLiteral` - Array construction (e.g., `[1, 2, 3]`)
+/// - `DictLiteral` - Dictionary construction (e.g., `{ "a": 1 }`)
+/// - `Tuple` - Tuple construction (e.g., `(dir, 4.0)`)
+/// - `Index` - Element access (e.g., `items[0]`)
+/// - `Range` - Half-open integer range (e.g., `0..n`), only valid as a `for` iterable
+/// - `Match` - Pattern match over a value (usable as statement or expression)
+/// - `If` - Conditional whose value is that of the branch taken
+/// - `Block` - Braced statements with an optional final value expression
+/// - `Cast` - Explicit numeric conversion (e.g., `x as i32`)
+/// - `Lambda` - Closure (e.g., `|x| x * 2`)
+/// - `Try` - Error propagation (e.g., `load(path)?`)
 ///
 /// # Examples
 ///
@@ -484,6 +901,17 @@ impl fmt::Display for Stmt {
 /// sqrt(x * x + y * y)   // Call
 /// position.x            // FieldAccess
 /// Color { r: 1.0, g: 0.5, b: 0.0, a: 1.0 }  // StructLiteral
+/// [1, 2, 3]             // ArrayLiteral
+/// { "sword": 10 }       // DictLiteral
+/// (dir, dist)           // Tuple
+/// items[i]              // Index
+/// match d { Dir.Left => 1, _ => 0 }  // Match
+/// enemy.hit(5)          // MethodCall
+/// if fast { 10.0 } else { 5.0 }      // If
+/// { let d = b - a; d * d }           // Block
+/// pos.x as i32                       // Cast
+/// |x| x * 2                          // Lambda
+/// parse_level(text)?                 // Try
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
@@ -503,6 +931,62 @@ pub enum Expr {
         fields: Vec<(String, Expr)>,
         span: Span,
     },
+    /// Array literal: `[expr1, expr2, ...]`
+    ArrayLiteral(Vec<Expr>, Span),
+    /// Dictionary literal: `{ key1: value1, key2: value2, ... }`, or `{}` when empty
+    DictLiteral(Vec<(Expr, Expr)>, Span),
+    /// Tuple expression: `(expr1, expr2, ...)` with at least two elements
+    Tuple(Vec<Expr>, Span),
+    /// Index expression: `array[index]`
+    Index(Box<Expr>, Box<Expr>, Span),
+    /// Half-open range `start..end` (end exclusive)
+    Range(Box<Expr>, Box<Expr>, Span),
+    /// Match expression: `match scrutinee { pattern => arm, ... }`
+    ///
+    /// `if let pattern = value { ... } else { ... }` is parsed as a match whose
+    /// second arm is a wildcard holding the else branch.
+    Match {
+        scrutinee: Box<Expr>,
+        arms: Vec<MatchArm>,
+        span: Span,
+    },
+    /// Method call: `receiver.method(args)`
+    MethodCall {
+        receiver: Box<Expr>,
+        method: String,
+        args: Vec<Expr>,
+        span: Span,
+    },
+    /// If expression: `if cond { ... } else { ... }`
+    ///
+    /// `then_branch` is an `Expr::Block`; `else_branch` is an `Expr::Block` or,
+    /// for `else if`, another `Expr::If`. Without an `else` it produces no value.
+    If {
+        cond: Box<Expr>,
+        then_branch: Box<Expr>,
+        else_branch: Option<Box<Expr>>,
+        span: Span,
+    },
+    /// Block expression: `{ stmts; value }` - the value is the final expression
+    /// without a trailing semicolon, if any
+    Block {
+        stmts: Vec<Stmt>,
+        value: Option<Box<Expr>>,
+        span: Span,
+    },
+    /// Cast expression: `expr as type` (type name as written, e.g. `"i32"`)
+    Cast(Box<Expr>, String, Span),
+    /// Closure: `|x, y: i32| body` - parameter names with their type annotations,
+    /// if any. The body sees the enclosing function's variables, which are
+    /// captured by value when the closure is created.
+    Lambda {
+        params: Vec<(String, Option<String>)>,
+        body: Box<Expr>,
+        span: Span,
+    },
+    /// Error propagation: `expr?` unwraps an `Ok`, or returns an `Err` from the
+    /// enclosing function
+    Try(Box<Expr>, Span),
 }
 
 impl Expr {
@@ -517,6 +1001,18 @@ impl Expr {
             Expr::Assign(_, _, s) => *s,
             Expr::CompoundAssign(_, _, _, s) => *s,
             Expr::StructLiteral { span, .. } => *span,
+            Expr::ArrayLiteral(_, s) => *s,
+            Expr::DictLiteral(_, s) => *s,
+            Expr::Tuple(_, s) => *s,
+            Expr::Index(_, _, s) => *s,
+            Expr::Range(_, _, s) => *s,
+            Expr::Match { span, .. } => *span,
+            Expr::MethodCall { span, .. } => *span,
+            Expr::If { span, .. } => *span,
+            Expr::Block { span, .. } => *span,
+            Expr::Cast(_, _, s) => *s,
+            Expr::Lambda { span, .. } => *span,
+            Expr::Try(_, s) => *s,
         }
     }
 }
@@ -553,14 +1049,213 @@ impl fmt::Display for Expr {
                 }
                 write!(f, " }}")
             }
+            Expr::ArrayLiteral(elements, _) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            Expr::DictLiteral(entries, _) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {}: {}", key, value)?;
+                }
+                if !entries.is_empty() {
+                    write!(f, " ")?;
+                }
+                write!(f, "}}")
+            }
+            Expr::Tuple(elements, _) => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Index(array, index, _) => write!(f, "{}[{}]", array, index),
+            Expr::Range(start, end, _) => write!(f, "{}..{}", start, end),
+            Expr::Match {
+                scrutinee, arms, ..
+            } => {
+                write!(f, "match {} {{ ", scrutinee)?;
+                for arm in arms {
+                    write!(f, "{}, ", arm)?;
+                }
+                write!(f, "}}")
+            }
+            Expr::MethodCall {
+                receiver,
+                method,
+                args,
+                ..
+            } => {
+                write!(f, "{}.{}(", receiver, method)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            Expr::If {
+                cond,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                write!(f, "if {} {}", cond, then_branch)?;
+                if let Some(else_branch) = else_branch {
+                    write!(f, " else {}", else_branch)?;
+                }
+                Ok(())
+            }
+            Expr::Block { stmts, value, .. } => {
+                write!(f, "{{ ")?;
+                for stmt in stmts {
+                    write!(f, "{} ", stmt)?;
+                }
+                if let Some(value) = value {
+                    write!(f, "{} ", value)?;
+                }
+                write!(f, "}}")
+            }
+            Expr::Cast(expr, ty, _) => write!(f, "({} as {})", expr, ty),
+            Expr::Lambda { params, body, .. } => {
+                write!(f, "|")?;
+                for (i, (name, ty)) in params.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", name)?;
+                    if let Some(ty) = ty {
+                        write!(f, ": {}", ty)?;
+                    }
+                }
+                write!(f, "| {}", body)
+            }
+            Expr::Try(expr, _) => write!(f, "{}?", expr),
+        }
+    }
+}
+
+/// A single `pattern => body` arm of a match expression.
+///
+/// Block arms (`Dir.Left => { ... }`) store their statements in `body` and
+/// their final expression, if any, in `value`; expression arms (`Dir.Left => 1`)
+/// store the expression in `value` and leave `body` empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub body: Vec<Stmt>,
+    pub value: Option<Expr>,
+    pub span: Span,
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} => ", self.pattern)?;
+        match &self.value {
+            Some(value) if self.body.is_empty() => write!(f, "{}", value),
+            value => {
+                write!(f, "{{ ")?;
+                for stmt in &self.body {
+                    write!(f, "{} ", stmt)?;
+                }
+                if let Some(value) = value {
+                    write!(f, "{} ", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// Match arm pattern.
+///
+/// # Examples
+///
+/// ```text
+/// _           // Wildcard
+/// 42          // Literal
+/// Dir.Left    // EnumVariant
+/// Some(node)  // Some
+/// None        // None
+/// Ok(value)   // Ok
+/// Err(e)      // Err
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Wildcard(Span),
+    Literal(Literal, Span),
+    EnumVariant {
+        enum_name: String,
+        variant: String,
+        span: Span,
+    },
+    /// `Some(name)` - matches an `Option` holding a value and binds it to `name`
+    /// in the arm (`_` binds nothing)
+    Some {
+        binding: String,
+        span: Span,
+    },
+    /// `None` - matches an empty `Option`
+    None(Span),
+    /// `Ok(name)` - matches a successful `Result`, binding its value
+    Ok {
+        binding: String,
+        span: Span,
+    },
+    /// `Err(name)` - matches a failed `Result`, binding its error
+    Err {
+        binding: String,
+        span: Span,
+    },
+}
+
+impl Pattern {
+    pub fn span(&self) -> Span {
+        match self {
+            Pattern::Wildcard(s) => *s,
+            Pattern::Literal(_, s) => *s,
+            Pattern::EnumVariant { span, .. } => *span,
+            Pattern::Some { span, .. } => *span,
+            Pattern::None(s) => *s,
+            Pattern::Ok { span, .. } | Pattern::Err { span, .. } => *span,
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Wildcard(_) => write!(f, "_"),
+            Pattern::Literal(lit, _) => write!(f, "{}", lit),
+            Pattern::EnumVariant {
+                enum_name, variant, ..
+            } => write!(f, "{}.{}", enum_name, variant),
+            Pattern::Some { binding, .. } => write!(f, "Some({})", binding),
+            Pattern::None(_) => write!(f, "None"),
+            Pattern::Ok { binding, .. } => write!(f, "Ok({})", binding),
+            Pattern::Err { binding, .. } => write!(f, "Err({})", binding),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
-    Int(i32),
-    Float(f32),
+    Int(i64),
+    Float(f64),
     Bool(bool),
     Str(String),
 }
@@ -582,6 +1277,12 @@ pub enum BinaryOp {
     Sub,
     Mul,
     Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
     Eq,
     Ne,
     Lt,
@@ -599,6 +1300,12 @@ impl fmt::Display for BinaryOp {
             BinaryOp::Sub => "-",
             BinaryOp::Mul => "*",
             BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitXor => "^",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
             BinaryOp::Eq => "==",
             BinaryOp::Ne => "!=",
             BinaryOp::Lt => "<",
@@ -616,6 +1323,8 @@ impl fmt::Display for BinaryOp {
 pub enum UnaryOp {
     Neg,
     Not,
+    /// Bitwise NOT (`~`)
+    BitNot,
 }
 
 impl fmt::Display for UnaryOp {
@@ -623,6 +1332,7 @@ impl fmt::Display for UnaryOp {
         let s = match self {
             UnaryOp::Neg => "-",
             UnaryOp::Not => "!",
+            UnaryOp::BitNot => "~",
         };
         write!(f, "{}", s)
     }
@@ -632,6 +1342,32 @@ impl fmt::Display for UnaryOp {
 pub enum CompoundOp {
     AddAssign,
     SubAssign,
+    MulAssign,
+    DivAssign,
+    RemAssign,
+    BitAndAssign,
+    BitOrAssign,
+    BitXorAssign,
+    ShlAssign,
+    ShrAssign,
+}
+
+impl CompoundOp {
+    /// The binary operator applied by the assignment (`+` for `+=`)
+    pub fn binary_op(self) -> BinaryOp {
+        match self {
+            CompoundOp::AddAssign => BinaryOp::Add,
+            CompoundOp::SubAssign => BinaryOp::Sub,
+            CompoundOp::MulAssign => BinaryOp::Mul,
+            CompoundOp::DivAssign => BinaryOp::Div,
+            CompoundOp::RemAssign => BinaryOp::Rem,
+            CompoundOp::BitAndAssign => BinaryOp::BitAnd,
+            CompoundOp::BitOrAssign => BinaryOp::BitOr,
+            CompoundOp::BitXorAssign => BinaryOp::BitXor,
+            CompoundOp::ShlAssign => BinaryOp::Shl,
+            CompoundOp::ShrAssign => BinaryOp::Shr,
+        }
+    }
 }
 
 impl fmt::Display for CompoundOp {
@@ -639,6 +1375,14 @@ impl fmt::Display for CompoundOp {
         let s = match self {
             CompoundOp::AddAssign => "+=",
             CompoundOp::SubAssign => "-=",
+            CompoundOp::MulAssign => "*=",
+            CompoundOp::DivAssign => "/=",
+            CompoundOp::RemAssign => "%=",
+            CompoundOp::BitAndAssign => "&=",
+            CompoundOp::BitOrAssign => "|=",
+            CompoundOp::BitXorAssign => "^=",
+            CompoundOp::ShlAssign => "<<=",
+            CompoundOp::ShrAssign => ">>=",
         };
         write!(f, "{}", s)
     }
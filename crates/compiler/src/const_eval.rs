@@ -0,0 +1,593 @@
+//! Compile-time evaluation of constant expressions.
+//!
+//! Folds expressions built from literals, other `const`s, unary and binary
+//! operators and `as` casts into a [`ConstValue`]. The type checker uses it
+//! to validate `const` declarations and `@export` defaults, the parser to read
+//! property hint bounds, and the runtime to inline constants.
+//!
+//! Evaluation follows the runtime: operands are promoted to the wider of their
+//! representations, integer overflow and division by zero are errors, and `as`
+//! casts truncate, saturate and wrap like Rust's. Float literals are `f32` unless
+//! the expression is evaluated for an `f64` (see [`eval_as`]).
+//!
+//! Constants may refer to constants declared later in the file;
+//! [`dependency_order`] gives the order to fold them in.
+//!
+//! # Example
+//!
+//! ```
+//! use ferrisscript_compiler::ast::{BinaryOp, Expr, Literal, Span};
+//! use ferrisscript_compiler::const_eval::{eval, ConstValue};
+//! use std::collections::HashMap;
+//!
+//! let mut consts = HashMap::new();
+//! consts.insert("BASE_HP".to_string(), ConstValue::I32(50));
+//!
+//! let expr = Expr::Binary(
+//!     Box::new(Expr::Variable("BASE_HP".to_string(), Span::unknown())),
+//!     BinaryOp::Mul,
+//!     Box::new(Expr::Literal(Literal::Int(2), Span::unknown())),
+//!     Span::unknown(),
+//! );
+//! assert_eq!(eval(&expr, &consts), Ok(ConstValue::I32(100)));
+//! ```
+
+use crate::ast::{BinaryOp, ConstDecl, Expr, Literal, UnaryOp};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The value of a constant expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Str(String),
+}
+
+impl ConstValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ConstValue::I32(_) => "i32",
+            ConstValue::I64(_) => "i64",
+            ConstValue::F32(_) => "f32",
+            ConstValue::F64(_) => "f64",
+            ConstValue::Bool(_) => "bool",
+            ConstValue::Str(_) => "String",
+        }
+    }
+
+    /// The value as an `f64`, or `None` if it is not a number
+    pub fn to_f64(&self) -> Option<f64> {
+        match self {
+            ConstValue::I32(i) => Some(f64::from(*i)),
+            ConstValue::I64(i) => Some(*i as f64),
+            ConstValue::F32(f) => Some(f64::from(*f)),
+            ConstValue::F64(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Widen the value to the declared type `ty`, as storing it into a variable
+    /// of that type would
+    pub fn convert(self, ty: &str) -> Result<ConstValue, String> {
+        Ok(match (ty, self) {
+            ("i32", value @ ConstValue::I32(_)) => value,
+            ("i64", ConstValue::I32(i)) => ConstValue::I64(i64::from(i)),
+            ("i64", value @ ConstValue::I64(_)) => value,
+            ("f32", ConstValue::I32(i)) => ConstValue::F32(i as f32),
+            ("f32", ConstValue::I64(i)) => ConstValue::F32(i as f32),
+            ("f32", value @ ConstValue::F32(_)) => value,
+            ("f64", ConstValue::I32(i)) => ConstValue::F64(f64::from(i)),
+            ("f64", ConstValue::I64(i)) => ConstValue::F64(i as f64),
+            ("f64", ConstValue::F32(f)) => ConstValue::F64(f64::from(f)),
+            ("f64", value @ ConstValue::F64(_)) => value,
+            ("bool", value @ ConstValue::Bool(_)) => value,
+            ("String", value @ ConstValue::Str(_)) => value,
+            (ty, value) => {
+                return Err(format!(
+                    "expected a value of type {}, found {}",
+                    ty,
+                    value.type_name()
+                ));
+            }
+        })
+    }
+}
+
+/// Formats the value as it would be written in source (strings are quoted)
+impl fmt::Display for ConstValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstValue::I32(i) => write!(f, "{}", i),
+            ConstValue::I64(i) => write!(f, "{}", i),
+            ConstValue::F32(x) => write!(f, "{}", x),
+            ConstValue::F64(x) => write!(f, "{}", x),
+            ConstValue::Bool(b) => write!(f, "{}", b),
+            ConstValue::Str(s) => write!(f, "\"{}\"", s),
+        }
+    }
+}
+
+/// Evaluate a constant expression. Variables must name entries of `consts`.
+pub fn eval(expr: &Expr, consts: &HashMap<String, ConstValue>) -> Result<ConstValue, String> {
+    Evaluator {
+        consts,
+        wide: false,
+    }
+    .eval(expr)
+}
+
+/// Evaluate a constant expression for a value of the declared type `ty` and
+/// convert the result to it. Float literals keep their full precision when `ty`
+/// is `f64`.
+pub fn eval_as(
+    expr: &Expr,
+    ty: &str,
+    consts: &HashMap<String, ConstValue>,
+) -> Result<ConstValue, String> {
+    Evaluator {
+        consts,
+        wide: ty == "f64",
+    }
+    .eval(expr)?
+    .convert(ty)
+}
+
+/// The order to fold `consts` in, so that each constant comes after the
+/// constants its value refers to, whatever order they were declared in.
+///
+/// Returns the indices of `consts` in that order, and the cycles of constants
+/// whose values refer to each other. A cycle lists the indices of its constants
+/// in the order they refer to each other, starting with the first declared;
+/// constants in a cycle are left out of the order.
+pub fn dependency_order(consts: &[ConstDecl]) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let mut indices = HashMap::new();
+    for (i, const_decl) in consts.iter().enumerate() {
+        indices.entry(const_decl.name.as_str()).or_insert(i);
+    }
+    let mut sorter = DependencySorter {
+        consts,
+        indices,
+        visited: vec![false; consts.len()],
+        cyclic: vec![false; consts.len()],
+        path: Vec::new(),
+        order: Vec::new(),
+        cycles: Vec::new(),
+    };
+    for i in 0..consts.len() {
+        if !sorter.visited[i] {
+            sorter.visit(i);
+        }
+    }
+    (sorter.order, sorter.cycles)
+}
+
+/// Depth-first topological sort over the constants' references
+struct DependencySorter<'a> {
+    consts: &'a [ConstDecl],
+    indices: HashMap<&'a str, usize>,
+    visited: Vec<bool>,
+    cyclic: Vec<bool>,
+    /// Constants whose references are being visited, outermost first
+    path: Vec<usize>,
+    order: Vec<usize>,
+    cycles: Vec<Vec<usize>>,
+}
+
+impl DependencySorter<'_> {
+    fn visit(&mut self, i: usize) {
+        self.visited[i] = true;
+        self.path.push(i);
+
+        let mut references = Vec::new();
+        references_of(&self.consts[i].value, &mut references);
+        for name in references {
+            let Some(&dependency) = self.indices.get(name) else {
+                continue;
+            };
+            if let Some(start) = self.path.iter().position(|&j| j == dependency) {
+                let cycle = self.path[start..].to_vec();
+                for &j in &cycle {
+                    self.cyclic[j] = true;
+                }
+                self.cycles.push(cycle);
+            } else if !self.visited[dependency] {
+                self.visit(dependency);
+            }
+        }
+
+        self.path.pop();
+        if !self.cyclic[i] {
+            self.order.push(i);
+        }
+    }
+}
+
+/// Names of the variables a constant expression refers to
+fn references_of<'a>(expr: &'a Expr, names: &mut Vec<&'a str>) {
+    match expr {
+        Expr::Variable(name, _) => names.push(name),
+        Expr::Unary(_, operand, _) | Expr::Cast(operand, _, _) => references_of(operand, names),
+        Expr::Binary(left, _, right, _) => {
+            references_of(left, names);
+            references_of(right, names);
+        }
+        _ => {}
+    }
+}
+
+struct Evaluator<'a> {
+    consts: &'a HashMap<String, ConstValue>,
+    /// Read float literals as `f64`
+    wide: bool,
+}
+
+impl Evaluator<'_> {
+    fn eval(&self, expr: &Expr) -> Result<ConstValue, String> {
+        match expr {
+            Expr::Literal(lit, _) => Ok(match lit {
+                Literal::Int(n) => match i32::try_from(*n) {
+                    Ok(i) => ConstValue::I32(i),
+                    Err(_) => ConstValue::I64(*n),
+                },
+                Literal::Float(f) if self.wide => ConstValue::F64(*f),
+                Literal::Float(f) => ConstValue::F32(*f as f32),
+                Literal::Bool(b) => ConstValue::Bool(*b),
+                Literal::Str(s) => ConstValue::Str(s.clone()),
+            }),
+            Expr::Variable(name, _) => self
+                .consts
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("'{}' is not a constant", name)),
+            Expr::Unary(op, operand, _) => unary(*op, self.eval(operand)?),
+            Expr::Binary(left, op @ (BinaryOp::And | BinaryOp::Or), right, _) => {
+                // Short-circuit like the runtime, so the right side may be skipped
+                let left = self.eval_bool(left, *op)?;
+                if left == (*op == BinaryOp::Or) {
+                    return Ok(ConstValue::Bool(left));
+                }
+                Ok(ConstValue::Bool(self.eval_bool(right, *op)?))
+            }
+            Expr::Binary(left, op, right, _) => binary(*op, self.eval(left)?, self.eval(right)?),
+            Expr::Cast(operand, ty, _) => cast(self.eval(operand)?, ty),
+            _ => Err(
+                "only literals, constants, operators and casts can be evaluated at compile time"
+                    .to_string(),
+            ),
+        }
+    }
+
+    fn eval_bool(&self, expr: &Expr, op: BinaryOp) -> Result<bool, String> {
+        match self.eval(expr)? {
+            ConstValue::Bool(b) => Ok(b),
+            other => Err(format!("cannot apply '{}' to {}", op, other.type_name())),
+        }
+    }
+}
+
+/// Numeric operands converted to a common representation
+enum Operands {
+    I32(i32, i32),
+    I64(i64, i64),
+    F32(f32, f32),
+    F64(f64, f64),
+}
+
+impl Operands {
+    /// Promote two values to the wider of their representations, or `None`
+    /// if either is not numeric
+    fn promote(left: &ConstValue, right: &ConstValue) -> Option<Operands> {
+        use ConstValue::*;
+        let wide = matches!(left, I64(_) | F64(_)) || matches!(right, I64(_) | F64(_));
+        Some(match (left, right) {
+            (I32(a), I32(b)) => Operands::I32(*a, *b),
+            (I32(a), I64(b)) => Operands::I64(i64::from(*a), *b),
+            (I64(a), I32(b)) => Operands::I64(*a, i64::from(*b)),
+            (I64(a), I64(b)) => Operands::I64(*a, *b),
+            _ if wide => Operands::F64(left.to_f64()?, right.to_f64()?),
+            _ => Operands::F32(left.to_f64()? as f32, right.to_f64()? as f32),
+        })
+    }
+}
+
+fn overflow(op: impl fmt::Display) -> String {
+    format!("integer overflow in '{}'", op)
+}
+
+fn unary(op: UnaryOp, operand: ConstValue) -> Result<ConstValue, String> {
+    Ok(match (op, operand) {
+        (UnaryOp::Neg, ConstValue::I32(i)) => {
+            ConstValue::I32(i.checked_neg().ok_or_else(|| overflow(op))?)
+        }
+        (UnaryOp::Neg, ConstValue::I64(i)) => {
+            ConstValue::I64(i.checked_neg().ok_or_else(|| overflow(op))?)
+        }
+        (UnaryOp::Neg, ConstValue::F32(f)) => ConstValue::F32(-f),
+        (UnaryOp::Neg, ConstValue::F64(f)) => ConstValue::F64(-f),
+        (UnaryOp::Not, ConstValue::Bool(b)) => ConstValue::Bool(!b),
+        (UnaryOp::BitNot, ConstValue::I32(i)) => ConstValue::I32(!i),
+        (UnaryOp::BitNot, ConstValue::I64(i)) => ConstValue::I64(!i),
+        (op, operand) => {
+            return Err(format!("cannot apply '{}' to {}", op, operand.type_name()));
+        }
+    })
+}
+
+fn binary(op: BinaryOp, left: ConstValue, right: ConstValue) -> Result<ConstValue, String> {
+    let mismatch = || {
+        format!(
+            "cannot apply '{}' to {} and {}",
+            op,
+            left.type_name(),
+            right.type_name()
+        )
+    };
+
+    match (op, &left, &right) {
+        (BinaryOp::Add, ConstValue::Str(a), ConstValue::Str(b)) => {
+            return Ok(ConstValue::Str(format!("{}{}", a, b)));
+        }
+        (BinaryOp::Eq | BinaryOp::Ne, ConstValue::Str(a), ConstValue::Str(b)) => {
+            return Ok(ConstValue::Bool((a == b) == (op == BinaryOp::Eq)));
+        }
+        (BinaryOp::Eq | BinaryOp::Ne, ConstValue::Bool(a), ConstValue::Bool(b)) => {
+            return Ok(ConstValue::Bool((a == b) == (op == BinaryOp::Eq)));
+        }
+        (BinaryOp::Shl | BinaryOp::Shr, _, _) => return shift(op, &left, &right),
+        _ => {}
+    }
+
+    let operands = Operands::promote(&left, &right).ok_or_else(mismatch)?;
+    let ordering = match &operands {
+        Operands::I32(a, b) => a.partial_cmp(b),
+        Operands::I64(a, b) => a.partial_cmp(b),
+        Operands::F32(a, b) => a.partial_cmp(b),
+        Operands::F64(a, b) => a.partial_cmp(b),
+    };
+    let compared = match op {
+        BinaryOp::Eq => Some(ordering == Some(Ordering::Equal)),
+        BinaryOp::Ne => Some(ordering != Some(Ordering::Equal)),
+        BinaryOp::Lt => Some(ordering == Some(Ordering::Less)),
+        BinaryOp::Le => Some(matches!(ordering, Some(Ordering::Less | Ordering::Equal))),
+        BinaryOp::Gt => Some(ordering == Some(Ordering::Greater)),
+        BinaryOp::Ge => Some(matches!(
+            ordering,
+            Some(Ordering::Greater | Ordering::Equal)
+        )),
+        _ => None,
+    };
+    if let Some(result) = compared {
+        return Ok(ConstValue::Bool(result));
+    }
+
+    let divides = matches!(op, BinaryOp::Div | BinaryOp::Rem);
+    let zero = match &operands {
+        Operands::I32(_, b) => *b == 0,
+        Operands::I64(_, b) => *b == 0,
+        Operands::F32(_, b) => *b == 0.0,
+        Operands::F64(_, b) => *b == 0.0,
+    };
+    if divides && zero {
+        return Err("division by zero".to_string());
+    }
+
+    match operands {
+        Operands::I32(a, b) => int_op(op, a, b)
+            .ok_or_else(mismatch)?
+            .map(ConstValue::I32)
+            .ok_or_else(|| overflow(op)),
+        Operands::I64(a, b) => int_op(op, a, b)
+            .ok_or_else(mismatch)?
+            .map(ConstValue::I64)
+            .ok_or_else(|| overflow(op)),
+        Operands::F32(a, b) => float_op(op, f64::from(a), f64::from(b))
+            .map(|f| ConstValue::F32(f as f32))
+            .ok_or_else(mismatch),
+        Operands::F64(a, b) => float_op(op, a, b).map(ConstValue::F64).ok_or_else(mismatch),
+    }
+}
+
+/// Checked integer arithmetic: `None` if `op` does not apply to integers,
+/// `Some(None)` on overflow
+fn int_op<T>(op: BinaryOp, a: T, b: T) -> Option<Option<T>>
+where
+    T: Copy
+        + std::ops::BitAnd<Output = T>
+        + std::ops::BitOr<Output = T>
+        + std::ops::BitXor<Output = T>
+        + CheckedInt,
+{
+    Some(match op {
+        BinaryOp::Add => a.add(b),
+        BinaryOp::Sub => a.sub(b),
+        BinaryOp::Mul => a.mul(b),
+        BinaryOp::Div => a.div(b),
+        BinaryOp::Rem => a.rem(b),
+        BinaryOp::BitAnd => Some(a & b),
+        BinaryOp::BitOr => Some(a | b),
+        BinaryOp::BitXor => Some(a ^ b),
+        _ => return None,
+    })
+}
+
+fn float_op(op: BinaryOp, a: f64, b: f64) -> Option<f64> {
+    Some(match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Mul => a * b,
+        BinaryOp::Div => a / b,
+        BinaryOp::Rem => a % b,
+        _ => return None,
+    })
+}
+
+/// The checked operations of `i32` and `i64`
+trait CheckedInt: Sized {
+    fn add(self, other: Self) -> Option<Self>;
+    fn sub(self, other: Self) -> Option<Self>;
+    fn mul(self, other: Self) -> Option<Self>;
+    fn div(self, other: Self) -> Option<Self>;
+    fn rem(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! checked_int {
+    ($($ty:ty),*) => {$(
+        impl CheckedInt for $ty {
+            fn add(self, other: Self) -> Option<Self> { self.checked_add(other) }
+            fn sub(self, other: Self) -> Option<Self> { self.checked_sub(other) }
+            fn mul(self, other: Self) -> Option<Self> { self.checked_mul(other) }
+            fn div(self, other: Self) -> Option<Self> { self.checked_div(other) }
+            fn rem(self, other: Self) -> Option<Self> { self.checked_rem(other) }
+        }
+    )*};
+}
+
+checked_int!(i32, i64);
+
+fn shift(op: BinaryOp, left: &ConstValue, right: &ConstValue) -> Result<ConstValue, String> {
+    let amount = match right {
+        ConstValue::I32(n) => u32::try_from(*n).ok(),
+        ConstValue::I64(n) => u32::try_from(*n).ok(),
+        _ => return Err(format!("cannot apply '{}' to {}", op, right.type_name())),
+    };
+    let result = match (op, left) {
+        (BinaryOp::Shl, ConstValue::I32(a)) => {
+            amount.and_then(|n| a.checked_shl(n)).map(ConstValue::I32)
+        }
+        (BinaryOp::Shl, ConstValue::I64(a)) => {
+            amount.and_then(|n| a.checked_shl(n)).map(ConstValue::I64)
+        }
+        (_, ConstValue::I32(a)) => amount.and_then(|n| a.checked_shr(n)).map(ConstValue::I32),
+        (_, ConstValue::I64(a)) => amount.and_then(|n| a.checked_shr(n)).map(ConstValue::I64),
+        _ => return Err(format!("cannot apply '{}' to {}", op, left.type_name())),
+    };
+    result.ok_or_else(|| format!("shift amount is out of range for {}", left.type_name()))
+}
+
+/// Apply an `as` cast with the runtime's semantics
+fn cast(value: ConstValue, ty: &str) -> Result<ConstValue, String> {
+    Ok(match (ty, value) {
+        ("i32", ConstValue::I64(i)) => ConstValue::I32(i as i32),
+        ("i32", ConstValue::F32(f)) => ConstValue::I32(f as i32),
+        ("i32", ConstValue::F64(f)) => ConstValue::I32(f as i32),
+        ("i32", ConstValue::Bool(b)) => ConstValue::I32(i32::from(b)),
+        ("i64", ConstValue::F32(f)) => ConstValue::I64(f as i64),
+        ("i64", ConstValue::F64(f)) => ConstValue::I64(f as i64),
+        ("i64", ConstValue::Bool(b)) => ConstValue::I64(i64::from(b)),
+        ("f32", ConstValue::F64(f)) => ConstValue::F32(f as f32),
+        (ty, value) => value
+            .convert(ty)
+            .map_err(|_| format!("cannot cast to {}", ty))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+    use crate::parser::parse;
+
+    /// Evaluate the initializer of the global `x` in `source`
+    fn fold(source: &str, consts: &[(&str, ConstValue)]) -> Result<ConstValue, String> {
+        let tokens = tokenize(source).unwrap();
+        let program = parse(&tokens, source).unwrap();
+        let consts = consts
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+        eval(&program.global_vars[0].value, &consts)
+    }
+
+    #[test]
+    fn test_fold_arithmetic() {
+        assert_eq!(fold("let x = 2 + 3 * 4;", &[]), Ok(ConstValue::I32(14)));
+        assert_eq!(fold("let x = -7 % 3;", &[]), Ok(ConstValue::I32(-1)));
+        assert_eq!(fold("let x = 1 << 4 | 1;", &[]), Ok(ConstValue::I32(17)));
+        assert_eq!(fold("let x = 1.5 * 2;", &[]), Ok(ConstValue::F32(3.0)));
+        assert_eq!(
+            fold("let x = \"a\" + \"b\";", &[]),
+            Ok(ConstValue::Str("ab".to_string()))
+        );
+        assert_eq!(
+            fold("let x = 3000000000 + 1;", &[]),
+            Ok(ConstValue::I64(3000000001))
+        );
+    }
+
+    #[test]
+    fn test_fold_constants_casts_and_logic() {
+        let consts = [("BASE", ConstValue::I32(50))];
+        assert_eq!(fold("let x = BASE * 2;", &consts), Ok(ConstValue::I32(100)));
+        assert_eq!(
+            fold("let x = BASE as f32 / 3.0;", &consts),
+            Ok(ConstValue::F32(50.0 / 3.0))
+        );
+        assert_eq!(fold("let x = 2.9 as i32;", &[]), Ok(ConstValue::I32(2)));
+        assert_eq!(
+            fold("let x = BASE > 10 && !false;", &consts),
+            Ok(ConstValue::Bool(true))
+        );
+        // The right side of a short-circuited `||` is never evaluated
+        assert_eq!(
+            fold("let x = true || 1 / 0 == 0;", &[]),
+            Ok(ConstValue::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_fold_errors() {
+        assert!(
+            fold("let x = 1 / 0;", &[])
+                .unwrap_err()
+                .contains("division by zero")
+        );
+        assert!(
+            fold("let x = 2147483647 + 1;", &[])
+                .unwrap_err()
+                .contains("overflow")
+        );
+        assert!(
+            fold("let x = y + 1;", &[])
+                .unwrap_err()
+                .contains("'y' is not a constant")
+        );
+        assert!(fold("let x = f();", &[]).is_err());
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        let source = "
+            const TOTAL: i32 = BASE * COUNT;
+            const COUNT: i32 = 3;
+            const BASE: i32 = COUNT + 1;
+            const A: i32 = B;
+            const B: i32 = A + TOTAL;
+            const SELF: i32 = SELF;
+            const C: i32 = D;
+            const D: i32 = C + E;
+            const E: i32 = D;
+        ";
+        let tokens = tokenize(source).unwrap();
+        let program = parse(&tokens, source).unwrap();
+        let (order, cycles) = dependency_order(&program.consts);
+        assert_eq!(order, vec![1, 2, 0]);
+        assert_eq!(cycles, vec![vec![3, 4], vec![5], vec![6, 7], vec![7, 8]]);
+    }
+
+    #[test]
+    fn test_eval_as_declared_type() {
+        let tokens = tokenize("let x = 0.1;").unwrap();
+        let program = parse(&tokens, "let x = 0.1;").unwrap();
+        let value = &program.global_vars[0].value;
+        let consts = HashMap::new();
+
+        assert_eq!(eval_as(value, "f64", &consts), Ok(ConstValue::F64(0.1)));
+        assert_eq!(eval_as(value, "f32", &consts), Ok(ConstValue::F32(0.1)));
+        assert!(eval_as(value, "i32", &consts).is_err());
+    }
+}
@@ -71,6 +71,8 @@ pub enum ErrorCode {
     E005,
     /// Unexpected end of file during lexing
     E006,
+    /// Invalid string interpolation
+    E007,
 
     // Syntax Errors (E100-E199)
     /// Expected a specific token but found another
@@ -143,6 +145,18 @@ pub enum ErrorCode {
     E218,
     /// Incompatible types in assignment
     E219,
+    /// If expression branches have incompatible types, or no else branch
+    E220,
+    /// Cast between types that have no conversion (e.g. `Vector2 as i32`)
+    E221,
+    /// `const` value that cannot be evaluated at compile time (or has an invalid type)
+    E222,
+    /// Call through a variable whose value is not a function
+    E223,
+    /// Assignment to a variable captured by a closure
+    E224,
+    /// `?` on a value that is not a `Result`, or in a function that does not return a compatible `Result`
+    E225,
 
     // Semantic Errors (E300-E399) - Signal-related errors and future semantic analysis
     /// Signal already defined (duplicate signal name)
@@ -164,36 +178,66 @@ pub enum ErrorCode {
     // E311: Invalid return (not in function)
 
     // Runtime Errors (E400-E499)
-    /// Division by zero
+    /// Cannot assign to immutable variable
     E400,
     /// Array index out of bounds
     E401,
-    /// Null pointer or None value access
+    /// Unknown built-in function
     E402,
-    /// Stack overflow
+    /// Stack overflow (maximum call depth exceeded)
     E403,
-    /// Memory exhaustion
+    /// Cannot set self properties (no setter)
     E404,
-    /// Godot API error
+    /// Cannot assign to field of immutable variable
     E405,
-    /// Undefined variable at runtime
+    /// Cannot assign value to Vector2 field
     E406,
-    /// Unknown built-in function
+    /// Vector2 has no field
     E407,
-    /// Invalid assignment target at runtime
+    /// Cannot access field on non-object
     E408,
-    /// Cannot assign value to immutable variable at runtime
+    /// Property is not a Vector2
     E409,
-    /// Invalid field assignment
+    /// Cannot get self properties (no getter)
     E410,
-    /// Field not found at runtime
-    /// Complex field assignment not implemented
+    /// Nested field assignment not yet implemented
     E411,
+    /// Complex field assignment not yet implemented
     E412,
-    /// Function not found at runtime
+    /// Division by zero
     E413,
-    /// Invalid function call at runtime
+    /// Cannot negate non-numeric value
     E414,
+    /// Undefined function
+    E415,
+    /// Wrong number of arguments
+    E416,
+    /// Cannot access self properties (no property getter)
+    E417,
+    /// Assignment expressions should be statements
+    E418,
+    /// Integer overflow
+    E419,
+    /// Instruction limit exceeded
+    E420,
+    /// Invalid number conversion
+    E421,
+    /// Value cannot be cast to the requested type
+    E422,
+    /// Called value is not a function
+    E423,
+    /// `?` reached a value that is not a `Result`
+    E424,
+    /// Undefined variable
+    E425,
+    /// Invalid assignment target
+    E426,
+
+    // Signal Errors (E500-E599)
+    /// emit_signal requires signal name
+    E501,
+    /// emit_signal signal name must be string
+    E502,
 
     // Type System Errors - Godot Types (E700-E799)
     /// Unknown field access on Color type
@@ -240,6 +284,56 @@ pub enum ErrorCode {
     E812,
     /// @export default value must be compile-time constant
     E813,
+
+    // Collection and Loop Errors (E900-E939)
+    /// Array literal elements have mismatched types
+    E900,
+    /// Cannot infer element type of empty array literal
+    E901,
+    /// Cannot index into non-array type
+    E903,
+    /// Array function called on non-array type
+    E904,
+    /// Dictionary literal entries have mismatched key or value types
+    E905,
+    /// Cannot infer key and value types of empty dictionary literal
+    E906,
+    /// Tuple element index is out of range
+    E907,
+    /// Destructuring `let` pattern does not match the value's type
+    E908,
+    /// For-in target is not iterable
+    E920,
+    /// `break` or `continue` outside of a loop
+    E921,
+    /// `break` or `continue` names a label no enclosing loop has
+    E922,
+    /// Range `start..end` used anywhere but a `for` header
+    E923,
+
+    // Enum and Match Errors (E940-E959)
+    /// Match does not cover every possible value
+    E940,
+    /// Match arm can never be reached
+    E941,
+    /// Match arms produce inconsistent types
+    E942,
+    /// Enum has no such variant
+    E943,
+
+    // Struct Errors (E960-E979)
+    /// Struct literal is missing declared fields
+    E960,
+
+    // Module Errors (E980-E999)
+    /// Module file named by `use` could not be loaded
+    E980,
+    /// Modules use each other in a cycle
+    E981,
+    /// Module declares something other than functions, constants, enums, structs and impls
+    E982,
+    /// `use` path does not give a valid module name, or the name is already in use
+    E983,
 }
 
 impl ErrorCode {
@@ -253,6 +347,7 @@ impl ErrorCode {
             ErrorCode::E004 => "E004",
             ErrorCode::E005 => "E005",
             ErrorCode::E006 => "E006",
+            ErrorCode::E007 => "E007",
 
             // Syntax Errors
             ErrorCode::E100 => "E100",
@@ -291,6 +386,12 @@ impl ErrorCode {
             ErrorCode::E217 => "E217",
             ErrorCode::E218 => "E218",
             ErrorCode::E219 => "E219",
+            ErrorCode::E220 => "E220",
+            ErrorCode::E221 => "E221",
+            ErrorCode::E222 => "E222",
+            ErrorCode::E223 => "E223",
+            ErrorCode::E224 => "E224",
+            ErrorCode::E225 => "E225",
 
             // Semantic Errors
             ErrorCode::E301 => "E301",
@@ -315,6 +416,22 @@ impl ErrorCode {
             ErrorCode::E412 => "E412",
             ErrorCode::E413 => "E413",
             ErrorCode::E414 => "E414",
+            ErrorCode::E415 => "E415",
+            ErrorCode::E416 => "E416",
+            ErrorCode::E417 => "E417",
+            ErrorCode::E418 => "E418",
+            ErrorCode::E419 => "E419",
+            ErrorCode::E420 => "E420",
+            ErrorCode::E421 => "E421",
+            ErrorCode::E422 => "E422",
+            ErrorCode::E423 => "E423",
+            ErrorCode::E424 => "E424",
+            ErrorCode::E425 => "E425",
+            ErrorCode::E426 => "E426",
+
+            // Signal Errors
+            ErrorCode::E501 => "E501",
+            ErrorCode::E502 => "E502",
 
             // Type System Errors - Godot Types
             ErrorCode::E701 => "E701",
@@ -340,6 +457,29 @@ impl ErrorCode {
             ErrorCode::E811 => "E811",
             ErrorCode::E812 => "E812",
             ErrorCode::E813 => "E813",
+
+            // Collection and Loop Errors
+            ErrorCode::E900 => "E900",
+            ErrorCode::E901 => "E901",
+            ErrorCode::E903 => "E903",
+            ErrorCode::E904 => "E904",
+            ErrorCode::E905 => "E905",
+            ErrorCode::E906 => "E906",
+            ErrorCode::E907 => "E907",
+            ErrorCode::E908 => "E908",
+            ErrorCode::E920 => "E920",
+            ErrorCode::E921 => "E921",
+            ErrorCode::E922 => "E922",
+            ErrorCode::E923 => "E923",
+            ErrorCode::E940 => "E940",
+            ErrorCode::E941 => "E941",
+            ErrorCode::E942 => "E942",
+            ErrorCode::E943 => "E943",
+            ErrorCode::E960 => "E960",
+            ErrorCode::E980 => "E980",
+            ErrorCode::E981 => "E981",
+            ErrorCode::E982 => "E982",
+            ErrorCode::E983 => "E983",
         }
     }
 
@@ -393,6 +533,137 @@ impl ErrorCode {
         }
     }
 
+    /// Looks up an error code from its string form (e.g., "E413")
+    pub fn from_code(code: &str) -> Option<ErrorCode> {
+        let code = match code {
+            "E001" => ErrorCode::E001,
+            "E002" => ErrorCode::E002,
+            "E003" => ErrorCode::E003,
+            "E004" => ErrorCode::E004,
+            "E005" => ErrorCode::E005,
+            "E006" => ErrorCode::E006,
+            "E007" => ErrorCode::E007,
+            "E100" => ErrorCode::E100,
+            "E101" => ErrorCode::E101,
+            "E102" => ErrorCode::E102,
+            "E103" => ErrorCode::E103,
+            "E104" => ErrorCode::E104,
+            "E105" => ErrorCode::E105,
+            "E106" => ErrorCode::E106,
+            "E107" => ErrorCode::E107,
+            "E108" => ErrorCode::E108,
+            "E109" => ErrorCode::E109,
+            "E110" => ErrorCode::E110,
+            "E111" => ErrorCode::E111,
+            "E112" => ErrorCode::E112,
+            "E113" => ErrorCode::E113,
+            "E200" => ErrorCode::E200,
+            "E201" => ErrorCode::E201,
+            "E202" => ErrorCode::E202,
+            "E203" => ErrorCode::E203,
+            "E204" => ErrorCode::E204,
+            "E205" => ErrorCode::E205,
+            "E206" => ErrorCode::E206,
+            "E207" => ErrorCode::E207,
+            "E208" => ErrorCode::E208,
+            "E209" => ErrorCode::E209,
+            "E210" => ErrorCode::E210,
+            "E211" => ErrorCode::E211,
+            "E212" => ErrorCode::E212,
+            "E213" => ErrorCode::E213,
+            "E214" => ErrorCode::E214,
+            "E215" => ErrorCode::E215,
+            "E216" => ErrorCode::E216,
+            "E217" => ErrorCode::E217,
+            "E218" => ErrorCode::E218,
+            "E219" => ErrorCode::E219,
+            "E220" => ErrorCode::E220,
+            "E221" => ErrorCode::E221,
+            "E222" => ErrorCode::E222,
+            "E223" => ErrorCode::E223,
+            "E224" => ErrorCode::E224,
+            "E225" => ErrorCode::E225,
+            "E301" => ErrorCode::E301,
+            "E302" => ErrorCode::E302,
+            "E303" => ErrorCode::E303,
+            "E304" => ErrorCode::E304,
+            "E305" => ErrorCode::E305,
+            "E400" => ErrorCode::E400,
+            "E401" => ErrorCode::E401,
+            "E402" => ErrorCode::E402,
+            "E403" => ErrorCode::E403,
+            "E404" => ErrorCode::E404,
+            "E405" => ErrorCode::E405,
+            "E406" => ErrorCode::E406,
+            "E407" => ErrorCode::E407,
+            "E408" => ErrorCode::E408,
+            "E409" => ErrorCode::E409,
+            "E410" => ErrorCode::E410,
+            "E411" => ErrorCode::E411,
+            "E412" => ErrorCode::E412,
+            "E413" => ErrorCode::E413,
+            "E414" => ErrorCode::E414,
+            "E415" => ErrorCode::E415,
+            "E416" => ErrorCode::E416,
+            "E417" => ErrorCode::E417,
+            "E418" => ErrorCode::E418,
+            "E419" => ErrorCode::E419,
+            "E420" => ErrorCode::E420,
+            "E421" => ErrorCode::E421,
+            "E422" => ErrorCode::E422,
+            "E423" => ErrorCode::E423,
+            "E424" => ErrorCode::E424,
+            "E425" => ErrorCode::E425,
+            "E426" => ErrorCode::E426,
+            "E501" => ErrorCode::E501,
+            "E502" => ErrorCode::E502,
+            "E701" => ErrorCode::E701,
+            "E702" => ErrorCode::E702,
+            "E703" => ErrorCode::E703,
+            "E704" => ErrorCode::E704,
+            "E705" => ErrorCode::E705,
+            "E706" => ErrorCode::E706,
+            "E707" => ErrorCode::E707,
+            "E708" => ErrorCode::E708,
+            "E709" => ErrorCode::E709,
+            "E710" => ErrorCode::E710,
+            "E802" => ErrorCode::E802,
+            "E803" => ErrorCode::E803,
+            "E804" => ErrorCode::E804,
+            "E805" => ErrorCode::E805,
+            "E806" => ErrorCode::E806,
+            "E807" => ErrorCode::E807,
+            "E808" => ErrorCode::E808,
+            "E810" => ErrorCode::E810,
+            "E811" => ErrorCode::E811,
+            "E812" => ErrorCode::E812,
+            "E813" => ErrorCode::E813,
+            "E900" => ErrorCode::E900,
+            "E901" => ErrorCode::E901,
+            "E903" => ErrorCode::E903,
+            "E904" => ErrorCode::E904,
+            "E905" => ErrorCode::E905,
+            "E906" => ErrorCode::E906,
+            "E907" => ErrorCode::E907,
+            "E908" => ErrorCode::E908,
+            "E920" => ErrorCode::E920,
+            "E921" => ErrorCode::E921,
+            "E922" => ErrorCode::E922,
+            "E923" => ErrorCode::E923,
+            "E940" => ErrorCode::E940,
+            "E941" => ErrorCode::E941,
+            "E942" => ErrorCode::E942,
+            "E943" => ErrorCode::E943,
+            "E960" => ErrorCode::E960,
+            "E980" => ErrorCode::E980,
+            "E981" => ErrorCode::E981,
+            "E982" => ErrorCode::E982,
+            "E983" => ErrorCode::E983,
+            _ => return None,
+        };
+        Some(code)
+    }
+
     /// Returns a human-readable description of the error
     pub fn description(&self) -> &'static str {
         match self {
@@ -403,6 +674,7 @@ impl ErrorCode {
             ErrorCode::E004 => "Invalid number format",
             ErrorCode::E005 => "Invalid identifier",
             ErrorCode::E006 => "Unexpected end of file",
+            ErrorCode::E007 => "Invalid string interpolation",
 
             // Syntax Errors
             ErrorCode::E100 => "Expected token",
@@ -441,6 +713,12 @@ impl ErrorCode {
             ErrorCode::E217 => "Invalid assignment target",
             ErrorCode::E218 => "Type annotation required",
             ErrorCode::E219 => "Incompatible types in assignment",
+            ErrorCode::E220 => "Incompatible if branch types",
+            ErrorCode::E221 => "Invalid cast",
+            ErrorCode::E222 => "Invalid constant expression",
+            ErrorCode::E223 => "Value is not callable",
+            ErrorCode::E224 => "Cannot assign to captured variable",
+            ErrorCode::E225 => "Invalid use of ? operator",
 
             // Semantic Errors
             ErrorCode::E301 => "Signal already defined",
@@ -450,21 +728,37 @@ impl ErrorCode {
             ErrorCode::E305 => "Invalid lifecycle function signature",
 
             // Runtime Errors
-            ErrorCode::E400 => "Division by zero",
+            ErrorCode::E400 => "Cannot assign to immutable variable",
             ErrorCode::E401 => "Index out of bounds",
-            ErrorCode::E402 => "Null pointer access",
+            ErrorCode::E402 => "Unknown built-in function",
             ErrorCode::E403 => "Stack overflow",
-            ErrorCode::E404 => "Memory exhaustion",
-            ErrorCode::E405 => "Godot API error",
-            ErrorCode::E406 => "Undefined variable",
-            ErrorCode::E407 => "Unknown built-in function",
-            ErrorCode::E408 => "Invalid assignment target",
-            ErrorCode::E409 => "Cannot assign to immutable variable",
-            ErrorCode::E410 => "Invalid field assignment",
-            ErrorCode::E411 => "Field not found",
-            ErrorCode::E412 => "Complex field assignment not implemented",
-            ErrorCode::E413 => "Function not found",
-            ErrorCode::E414 => "Invalid function call",
+            ErrorCode::E404 => "Cannot set self properties (no setter)",
+            ErrorCode::E405 => "Cannot assign to field of immutable variable",
+            ErrorCode::E406 => "Cannot assign value to Vector2 field",
+            ErrorCode::E407 => "Vector2 has no field",
+            ErrorCode::E408 => "Cannot access field on non-object",
+            ErrorCode::E409 => "Property is not a Vector2",
+            ErrorCode::E410 => "Cannot get self properties (no getter)",
+            ErrorCode::E411 => "Nested field assignment not yet implemented",
+            ErrorCode::E412 => "Complex field assignment not yet implemented",
+            ErrorCode::E413 => "Division by zero",
+            ErrorCode::E414 => "Cannot negate non-numeric value",
+            ErrorCode::E415 => "Undefined function",
+            ErrorCode::E416 => "Wrong number of arguments",
+            ErrorCode::E417 => "Cannot access self properties (no property getter)",
+            ErrorCode::E418 => "Assignment expressions should be statements",
+            ErrorCode::E419 => "Integer overflow",
+            ErrorCode::E420 => "Instruction limit exceeded",
+            ErrorCode::E421 => "Invalid number conversion",
+            ErrorCode::E422 => "Invalid cast",
+            ErrorCode::E423 => "Value is not callable",
+            ErrorCode::E424 => "Invalid ? operand",
+            ErrorCode::E425 => "Undefined variable",
+            ErrorCode::E426 => "Invalid assignment target",
+
+            // Signal Errors
+            ErrorCode::E501 => "emit_signal requires signal name",
+            ErrorCode::E502 => "emit_signal signal name must be string",
 
             // Type System Errors - Godot Types
             ErrorCode::E701 => "Unknown field on Color",
@@ -490,6 +784,29 @@ impl ErrorCode {
             ErrorCode::E811 => "@export on non-global scope",
             ErrorCode::E812 => "@export on immutable variable",
             ErrorCode::E813 => "@export default value must be compile-time constant",
+
+            // Collection and Loop Errors
+            ErrorCode::E900 => "Array literal type mismatch",
+            ErrorCode::E901 => "Cannot infer type of empty array literal",
+            ErrorCode::E903 => "Cannot index into non-array type",
+            ErrorCode::E904 => "Array function called on non-array type",
+            ErrorCode::E905 => "Dictionary literal type mismatch",
+            ErrorCode::E906 => "Cannot infer type of empty dictionary literal",
+            ErrorCode::E907 => "Tuple index out of range",
+            ErrorCode::E908 => "Destructuring pattern does not match value",
+            ErrorCode::E920 => "For-in target is not iterable",
+            ErrorCode::E921 => "Loop control outside of a loop",
+            ErrorCode::E922 => "Undefined loop label",
+            ErrorCode::E923 => "Range outside of a for loop",
+            ErrorCode::E940 => "Non-exhaustive match",
+            ErrorCode::E941 => "Unreachable match arm",
+            ErrorCode::E942 => "Inconsistent match arm types",
+            ErrorCode::E943 => "Unknown enum variant",
+            ErrorCode::E960 => "Missing field in struct literal",
+            ErrorCode::E980 => "Module not found",
+            ErrorCode::E981 => "Circular module use",
+            ErrorCode::E982 => "Declaration not allowed in a module",
+            ErrorCode::E983 => "Invalid module name",
         }
     }
 
@@ -502,7 +819,8 @@ impl ErrorCode {
             | ErrorCode::E003
             | ErrorCode::E004
             | ErrorCode::E005
-            | ErrorCode::E006 => ErrorCategory::Lexical,
+            | ErrorCode::E006
+            | ErrorCode::E007 => ErrorCategory::Lexical,
 
             // Syntax Errors
             ErrorCode::E100
@@ -540,7 +858,13 @@ impl ErrorCode {
             | ErrorCode::E216
             | ErrorCode::E217
             | ErrorCode::E218
-            | ErrorCode::E219 => ErrorCategory::Type,
+            | ErrorCode::E219
+            | ErrorCode::E220
+            | ErrorCode::E221
+            | ErrorCode::E222
+            | ErrorCode::E223
+            | ErrorCode::E224
+            | ErrorCode::E225 => ErrorCategory::Type,
 
             // Semantic Errors
             ErrorCode::E301
@@ -564,7 +888,21 @@ impl ErrorCode {
             | ErrorCode::E411
             | ErrorCode::E412
             | ErrorCode::E413
-            | ErrorCode::E414 => ErrorCategory::Runtime,
+            | ErrorCode::E414
+            | ErrorCode::E415
+            | ErrorCode::E416
+            | ErrorCode::E417
+            | ErrorCode::E418
+            | ErrorCode::E419
+            | ErrorCode::E420
+            | ErrorCode::E421
+            | ErrorCode::E422
+            | ErrorCode::E423
+            | ErrorCode::E424
+            | ErrorCode::E425
+            | ErrorCode::E426
+            | ErrorCode::E501
+            | ErrorCode::E502 => ErrorCategory::Runtime,
 
             // Type System Errors - Godot Types
             ErrorCode::E701
@@ -590,6 +928,30 @@ impl ErrorCode {
             | ErrorCode::E811
             | ErrorCode::E812
             | ErrorCode::E813 => ErrorCategory::Type,
+
+            // Collection and Loop Errors
+            ErrorCode::E900
+            | ErrorCode::E901
+            | ErrorCode::E903
+            | ErrorCode::E904
+            | ErrorCode::E905
+            | ErrorCode::E906
+            | ErrorCode::E907
+            | ErrorCode::E908
+            | ErrorCode::E920
+            | ErrorCode::E921
+            | ErrorCode::E922
+            | ErrorCode::E923
+            | ErrorCode::E940
+            | ErrorCode::E941
+            | ErrorCode::E942
+            | ErrorCode::E943
+            | ErrorCode::E960 => ErrorCategory::Type,
+
+            // Module Errors
+            ErrorCode::E980 | ErrorCode::E981 | ErrorCode::E982 | ErrorCode::E983 => {
+                ErrorCategory::Semantic
+            }
         }
     }
 
@@ -615,6 +977,26 @@ mod tests {
     fn test_error_code_description() {
         assert_eq!(ErrorCode::E002.description(), "Unterminated string literal");
         assert_eq!(ErrorCode::E201.description(), "Undefined variable");
+        assert_eq!(ErrorCode::E401.description(), "Index out of bounds");
+        assert_eq!(ErrorCode::E403.description(), "Stack overflow");
+    }
+
+    #[test]
+    fn test_error_code_from_code() {
+        for code in [
+            ErrorCode::E001,
+            ErrorCode::E215,
+            ErrorCode::E413,
+            ErrorCode::E425,
+            ErrorCode::E426,
+            ErrorCode::E502,
+            ErrorCode::E960,
+            ErrorCode::E983,
+        ] {
+            assert_eq!(ErrorCode::from_code(code.as_str()), Some(code));
+        }
+        assert_eq!(ErrorCode::from_code("E999"), None);
+        assert_eq!(ErrorCode::from_code("e413"), None);
     }
 
     #[test]
@@ -650,6 +1032,7 @@ mod tests {
             ErrorCode::E004,
             ErrorCode::E005,
             ErrorCode::E006,
+            ErrorCode::E007,
         ];
         for code in codes {
             assert_eq!(code.category(), ErrorCategory::Lexical);
@@ -706,6 +1089,40 @@ mod tests {
             ErrorCode::E217,
             ErrorCode::E218,
             ErrorCode::E219,
+            ErrorCode::E220,
+            ErrorCode::E221,
+            ErrorCode::E222,
+            ErrorCode::E223,
+            ErrorCode::E224,
+            ErrorCode::E225,
+        ];
+        for code in codes {
+            assert_eq!(code.category(), ErrorCategory::Type);
+            assert!(!code.as_str().is_empty());
+            assert!(!code.description().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_all_collection_and_match_errors() {
+        let codes = vec![
+            ErrorCode::E900,
+            ErrorCode::E901,
+            ErrorCode::E903,
+            ErrorCode::E904,
+            ErrorCode::E905,
+            ErrorCode::E906,
+            ErrorCode::E907,
+            ErrorCode::E908,
+            ErrorCode::E920,
+            ErrorCode::E921,
+            ErrorCode::E922,
+            ErrorCode::E923,
+            ErrorCode::E940,
+            ErrorCode::E941,
+            ErrorCode::E942,
+            ErrorCode::E943,
+            ErrorCode::E960,
         ];
         for code in codes {
             assert_eq!(code.category(), ErrorCategory::Type);
@@ -732,6 +1149,20 @@ mod tests {
             ErrorCode::E412,
             ErrorCode::E413,
             ErrorCode::E414,
+            ErrorCode::E415,
+            ErrorCode::E416,
+            ErrorCode::E417,
+            ErrorCode::E418,
+            ErrorCode::E419,
+            ErrorCode::E420,
+            ErrorCode::E421,
+            ErrorCode::E422,
+            ErrorCode::E423,
+            ErrorCode::E424,
+            ErrorCode::E425,
+            ErrorCode::E426,
+            ErrorCode::E501,
+            ErrorCode::E502,
         ];
         for code in codes {
             assert_eq!(code.category(), ErrorCategory::Runtime);
@@ -808,4 +1239,19 @@ mod tests {
         );
         assert_eq!(ErrorCode::E305.category(), ErrorCategory::Semantic);
     }
+
+    #[test]
+    fn test_all_module_errors() {
+        let codes = vec![
+            ErrorCode::E980,
+            ErrorCode::E981,
+            ErrorCode::E982,
+            ErrorCode::E983,
+        ];
+        for code in codes {
+            assert_eq!(code.category(), ErrorCategory::Semantic);
+            assert!(!code.as_str().is_empty());
+            assert!(!code.description().is_empty());
+        }
+    }
 }
@@ -39,6 +39,7 @@ pub enum Token {
     // Keywords
     Fn,
     Let,
+    Const,
     Mut,
     If,
     Else,
@@ -48,23 +49,44 @@ pub enum Token {
     False,
     Signal,
     Export,
+    For,
+    In,
+    Enum,
+    Match,
+    Struct,
+    Impl,
+    Loop,
+    Break,
+    Continue,
+    As,
+    Use,
 
     // Special symbols
     At, // @
 
     // Literals
     Ident(String),
-    Number(f32),
+    /// Integer literal (no decimal point); carried exactly up to `i64::MAX`
+    Int(i64),
+    /// Float literal (contains a decimal point)
+    Float(f64),
     StringLit(String),
+    /// String literal with `{expr}` interpolations, e.g. `"HP: {hp}"`
+    InterpolatedString(Vec<StringPart>),
+    /// Loop label such as `'outer` (without the quote)
+    Label(String),
 
     // Delimiters
     LParen,    // (
     RParen,    // )
     LBrace,    // {
     RBrace,    // }
+    LBracket,  // [
+    RBracket,  // ]
     Comma,     // ,
     Semicolon, // ;
     Dot,       // .
+    DotDot,    // ..
     Colon,     // :
 
     // Operators
@@ -72,6 +94,7 @@ pub enum Token {
     Minus,        // -
     Star,         // *
     Slash,        // /
+    Percent,      // %
     Equal,        // =
     EqualEqual,   // ==
     NotEqual,     // !=
@@ -84,6 +107,22 @@ pub enum Token {
     Not,          // !
     PlusEqual,    // +=
     MinusEqual,   // -=
+    StarEqual,    // *=
+    SlashEqual,   // /=
+    PercentEqual, // %=
+    FatArrow,     // =>
+    Amp,          // & (bitwise AND, and `&self` / `&mut self`)
+    Pipe,         // |
+    Caret,        // ^
+    Tilde,        // ~
+    Shl,          // <<
+    Shr,          // >>
+    AmpEqual,     // &=
+    PipeEqual,    // |=
+    CaretEqual,   // ^=
+    ShlEqual,     // <<=
+    ShrEqual,     // >>=
+    Question,     // ? (error propagation)
 
     // Special
     Eof,
@@ -95,6 +134,7 @@ impl Token {
         match self {
             Token::Fn => "fn",
             Token::Let => "let",
+            Token::Const => "const",
             Token::Mut => "mut",
             Token::If => "if",
             Token::Else => "else",
@@ -104,22 +144,38 @@ impl Token {
             Token::False => "false",
             Token::Signal => "signal",
             Token::Export => "export",
+            Token::For => "for",
+            Token::In => "in",
+            Token::Enum => "enum",
+            Token::Match => "match",
+            Token::Struct => "struct",
+            Token::Impl => "impl",
+            Token::Loop => "loop",
+            Token::Break => "break",
+            Token::Continue => "continue",
+            Token::As => "as",
+            Token::Use => "use",
             Token::At => "@",
             Token::Ident(_) => "identifier",
-            Token::Number(_) => "number",
-            Token::StringLit(_) => "string",
+            Token::Int(_) | Token::Float(_) => "number",
+            Token::StringLit(_) | Token::InterpolatedString(_) => "string",
+            Token::Label(_) => "label",
             Token::LParen => "(",
             Token::RParen => ")",
             Token::LBrace => "{",
             Token::RBrace => "}",
+            Token::LBracket => "[",
+            Token::RBracket => "]",
             Token::Comma => ",",
             Token::Semicolon => ";",
             Token::Dot => ".",
+            Token::DotDot => "..",
             Token::Colon => ":",
             Token::Plus => "+",
             Token::Minus => "-",
             Token::Star => "*",
             Token::Slash => "/",
+            Token::Percent => "%",
             Token::Equal => "=",
             Token::EqualEqual => "==",
             Token::NotEqual => "!=",
@@ -132,11 +188,36 @@ impl Token {
             Token::Not => "!",
             Token::PlusEqual => "+=",
             Token::MinusEqual => "-=",
+            Token::StarEqual => "*=",
+            Token::SlashEqual => "/=",
+            Token::PercentEqual => "%=",
+            Token::FatArrow => "=>",
+            Token::Amp => "&",
+            Token::Pipe => "|",
+            Token::Caret => "^",
+            Token::Tilde => "~",
+            Token::Shl => "<<",
+            Token::Shr => ">>",
+            Token::AmpEqual => "&=",
+            Token::PipeEqual => "|=",
+            Token::CaretEqual => "^=",
+            Token::ShlEqual => "<<=",
+            Token::ShrEqual => ">>=",
+            Token::Question => "?",
             Token::Eof => "end of file",
         }
     }
 }
 
+/// A piece of an interpolated string literal
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringPart {
+    /// Literal text, with escapes and `{{` / `}}` already resolved
+    Text(String),
+    /// Tokens of an embedded expression, ending with `Token::Eof`
+    Expr(Vec<PositionedToken>),
+}
+
 /// A token with its source location information.
 ///
 /// This structure wraps a `Token` with its line and column position in the source code,
@@ -144,8 +225,12 @@ impl Token {
 #[derive(Debug, Clone, PartialEq)]
 pub struct PositionedToken {
     pub token: Token,
+    /// Position of the token's first character
     pub line: usize,
     pub column: usize,
+    /// Position just past the token's last character
+    pub end_line: usize,
+    pub end_column: usize,
 }
 
 impl PositionedToken {
@@ -154,8 +239,17 @@ impl PositionedToken {
             token,
             line,
             column,
+            end_line: line,
+            end_column: column,
         }
     }
+
+    /// Set the position just past the token's last character
+    pub fn with_end(mut self, line: usize, column: usize) -> Self {
+        self.end_line = line;
+        self.end_column = column;
+        self
+    }
 }
 
 struct Lexer<'a> {
@@ -215,6 +309,16 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Finish an operator that has a compound assignment form, such as `*` / `*=`
+    fn operator_or_assign(&mut self, operator: Token, assign: Token) -> Token {
+        if self.current() == Some('=') {
+            self.advance();
+            assign
+        } else {
+            operator
+        }
+    }
+
     fn read_identifier(&mut self) -> String {
         let mut ident = String::new();
         while let Some(ch) = self.current() {
@@ -233,7 +337,17 @@ impl<'a> Lexer<'a> {
         let start_line = self.line;
         let start_col = self.column;
 
+        // A number right after a single `.` is a tuple index, so `pair.0.1` lexes
+        // as two indexes rather than the float `0.1`
+        let tuple_index = self.position >= 1
+            && self.input[self.position - 1] == '.'
+            && (self.position < 2 || self.input[self.position - 2] != '.');
+
         while let Some(ch) = self.current() {
+            // Stop before a range operator so `0..n` lexes as Int, DotDot, Ident
+            if ch == '.' && (tuple_index || self.peek(1) == Some('.')) {
+                break;
+            }
             if ch.is_numeric() || ch == '.' {
                 num_str.push(ch);
                 self.advance();
@@ -242,7 +356,12 @@ impl<'a> Lexer<'a> {
             }
         }
 
-        num_str.parse::<f32>().map(Token::Number).map_err(|_| {
+        let token = if num_str.contains('.') {
+            num_str.parse::<f64>().map(Token::Float).ok()
+        } else {
+            num_str.parse::<i64>().map(Token::Int).ok()
+        };
+        token.ok_or_else(|| {
             format!(
                 "Error[E003]: Invalid number '{}' at line {}, column {}",
                 num_str, start_line, start_col
@@ -256,6 +375,7 @@ impl<'a> Lexer<'a> {
 
         self.advance(); // consume opening quote
         let mut string = String::new();
+        let mut parts = Vec::new();
 
         loop {
             match self.current() {
@@ -333,6 +453,20 @@ impl<'a> Lexer<'a> {
                         }
                     }
                 }
+                // `{{` and `}}` are literal braces
+                Some(brace @ ('{' | '}')) if self.peek(1) == Some(brace) => {
+                    string.push(brace);
+                    self.advance();
+                    self.advance();
+                }
+                Some('{') => {
+                    if !string.is_empty() {
+                        parts.push(StringPart::Text(std::mem::take(&mut string)));
+                    }
+                    parts.push(StringPart::Expr(
+                        self.read_interpolation(start_line, start_col)?,
+                    ));
+                }
                 Some(ch) => {
                     string.push(ch);
                     self.advance();
@@ -340,7 +474,110 @@ impl<'a> Lexer<'a> {
             }
         }
 
-        Ok(Token::StringLit(string))
+        if parts.is_empty() {
+            return Ok(Token::StringLit(string));
+        }
+        if !string.is_empty() {
+            parts.push(StringPart::Text(string));
+        }
+        Ok(Token::InterpolatedString(parts))
+    }
+
+    /// Read the tokens of an expression interpolated into a string literal, from
+    /// its opening `{` up to and including the matching `}`.
+    ///
+    /// `start_line` and `start_col` locate the string's opening quote, which is
+    /// reported if the source ends before the string is closed.
+    fn read_interpolation(
+        &mut self,
+        start_line: usize,
+        start_col: usize,
+    ) -> Result<Vec<PositionedToken>, String> {
+        let (open_line, open_col) = (self.line, self.column);
+        self.advance(); // consume '{'
+
+        let mut tokens = Vec::new();
+        let mut depth = 0;
+        loop {
+            self.skip_whitespace();
+            let (line, column) = (self.line, self.column);
+            if self.current().is_none() {
+                let base_msg = format!(
+                    "Unterminated string at line {}, column {}",
+                    start_line, start_col
+                );
+                return Err(format_error_with_code(
+                    ErrorCode::E002,
+                    &base_msg,
+                    self.source,
+                    start_line,
+                    start_col,
+                    "String must be closed with \"",
+                ));
+            }
+            if self.current() == Some('"') {
+                let base_msg = format!(
+                    "Unterminated interpolation in string literal at line {}, column {}",
+                    open_line, open_col
+                );
+                return Err(format_error_with_code(
+                    ErrorCode::E007,
+                    &base_msg,
+                    self.source,
+                    open_line,
+                    open_col,
+                    "Close the interpolation with '}' (string literals cannot be nested inside one)",
+                ));
+            }
+            let token = self.next_token()?;
+            match token {
+                Token::RBrace if depth == 0 => {
+                    if tokens.is_empty() {
+                        let base_msg = format!(
+                            "Empty interpolation in string literal at line {}, column {}",
+                            open_line, open_col
+                        );
+                        return Err(format_error_with_code(
+                            ErrorCode::E007,
+                            &base_msg,
+                            self.source,
+                            open_line,
+                            open_col,
+                            "Put an expression between the braces, or use '{{' for a literal brace",
+                        ));
+                    }
+                    tokens.push(PositionedToken::new(Token::Eof, line, column));
+                    return Ok(tokens);
+                }
+                Token::LBrace => depth += 1,
+                Token::RBrace => depth -= 1,
+                _ => {}
+            }
+            tokens.push(PositionedToken::new(token, line, column).with_end(self.line, self.column));
+        }
+    }
+
+    /// Read a loop label such as `'outer`
+    fn read_label(&mut self) -> Result<Token, String> {
+        let (line, column) = (self.line, self.column);
+        self.advance(); // consume '\''
+        match self.current() {
+            Some(ch) if ch.is_alphabetic() || ch == '_' => Ok(Token::Label(self.read_identifier())),
+            _ => {
+                let base_msg = format!(
+                    "Unexpected character '\'' at line {}, column {}",
+                    line, column
+                );
+                Err(format_error_with_code(
+                    ErrorCode::E001,
+                    &base_msg,
+                    self.source,
+                    line,
+                    column,
+                    "A quote must start a loop label (e.g., 'outer: while ...)",
+                ))
+            }
+        }
     }
 
     fn next_token(&mut self) -> Result<Token, String> {
@@ -357,6 +594,7 @@ impl<'a> Lexer<'a> {
             let token = match ident.as_str() {
                 "fn" => Token::Fn,
                 "let" => Token::Let,
+                "const" => Token::Const,
                 "mut" => Token::Mut,
                 "if" => Token::If,
                 "else" => Token::Else,
@@ -366,6 +604,17 @@ impl<'a> Lexer<'a> {
                 "false" => Token::False,
                 "signal" => Token::Signal,
                 "export" => Token::Export,
+                "for" => Token::For,
+                "in" => Token::In,
+                "enum" => Token::Enum,
+                "match" => Token::Match,
+                "struct" => Token::Struct,
+                "impl" => Token::Impl,
+                "loop" => Token::Loop,
+                "break" => Token::Break,
+                "continue" => Token::Continue,
+                "as" => Token::As,
+                "use" => Token::Use,
                 _ => Token::Ident(ident),
             };
             return Ok(token);
@@ -381,39 +630,49 @@ impl<'a> Lexer<'a> {
             return self.read_string();
         }
 
+        // Loop labels
+        if ch == '\'' {
+            return self.read_label();
+        }
+
         // Multi-character operators
         let token = match ch {
             '+' => {
                 self.advance();
-                if self.current() == Some('=') {
-                    self.advance();
-                    Token::PlusEqual
-                } else {
-                    Token::Plus
-                }
+                self.operator_or_assign(Token::Plus, Token::PlusEqual)
             }
             '-' => {
                 self.advance();
-                if self.current() == Some('=') {
-                    self.advance();
-                    Token::MinusEqual
-                } else {
-                    Token::Minus
-                }
+                self.operator_or_assign(Token::Minus, Token::MinusEqual)
             }
             '*' => {
                 self.advance();
-                Token::Star
+                self.operator_or_assign(Token::Star, Token::StarEqual)
             }
             '/' => {
                 self.advance();
-                Token::Slash
+                self.operator_or_assign(Token::Slash, Token::SlashEqual)
+            }
+            '%' => {
+                self.advance();
+                self.operator_or_assign(Token::Percent, Token::PercentEqual)
+            }
+            '^' => {
+                self.advance();
+                self.operator_or_assign(Token::Caret, Token::CaretEqual)
+            }
+            '~' => {
+                self.advance();
+                Token::Tilde
             }
             '=' => {
                 self.advance();
                 if self.current() == Some('=') {
                     self.advance();
                     Token::EqualEqual
+                } else if self.current() == Some('>') {
+                    self.advance();
+                    Token::FatArrow
                 } else {
                     Token::Equal
                 }
@@ -429,64 +688,38 @@ impl<'a> Lexer<'a> {
             }
             '<' => {
                 self.advance();
-                if self.current() == Some('=') {
+                if self.current() == Some('<') {
                     self.advance();
-                    Token::LessEqual
+                    self.operator_or_assign(Token::Shl, Token::ShlEqual)
                 } else {
-                    Token::Less
+                    self.operator_or_assign(Token::Less, Token::LessEqual)
                 }
             }
             '>' => {
                 self.advance();
-                if self.current() == Some('=') {
+                if self.current() == Some('>') {
                     self.advance();
-                    Token::GreaterEqual
+                    self.operator_or_assign(Token::Shr, Token::ShrEqual)
                 } else {
-                    Token::Greater
+                    self.operator_or_assign(Token::Greater, Token::GreaterEqual)
                 }
             }
             '&' => {
-                let error_line = self.line;
-                let error_col = self.column;
                 self.advance();
                 if self.current() == Some('&') {
                     self.advance();
                     Token::And
                 } else {
-                    let base_msg = format!(
-                        "Unexpected character '&' at line {}, column {}",
-                        error_line, error_col
-                    );
-                    return Err(format_error_with_code(
-                        ErrorCode::E001,
-                        &base_msg,
-                        self.source,
-                        error_line,
-                        error_col,
-                        "Did you mean '&&' for logical AND?",
-                    ));
+                    self.operator_or_assign(Token::Amp, Token::AmpEqual)
                 }
             }
             '|' => {
-                let error_line = self.line;
-                let error_col = self.column;
                 self.advance();
                 if self.current() == Some('|') {
                     self.advance();
                     Token::Or
                 } else {
-                    let base_msg = format!(
-                        "Unexpected character '|' at line {}, column {}",
-                        error_line, error_col
-                    );
-                    return Err(format_error_with_code(
-                        ErrorCode::E001,
-                        &base_msg,
-                        self.source,
-                        error_line,
-                        error_col,
-                        "Did you mean '||' for logical OR?",
-                    ));
+                    self.operator_or_assign(Token::Pipe, Token::PipeEqual)
                 }
             }
             '(' => {
@@ -505,6 +738,14 @@ impl<'a> Lexer<'a> {
                 self.advance();
                 Token::RBrace
             }
+            '[' => {
+                self.advance();
+                Token::LBracket
+            }
+            ']' => {
+                self.advance();
+                Token::RBracket
+            }
             ',' => {
                 self.advance();
                 Token::Comma
@@ -515,7 +756,12 @@ impl<'a> Lexer<'a> {
             }
             '.' => {
                 self.advance();
-                Token::Dot
+                if self.current() == Some('.') {
+                    self.advance();
+                    Token::DotDot
+                } else {
+                    Token::Dot
+                }
             }
             ':' => {
                 self.advance();
@@ -525,6 +771,10 @@ impl<'a> Lexer<'a> {
                 self.advance();
                 Token::At
             }
+            '?' => {
+                self.advance();
+                Token::Question
+            }
             _ => {
                 let base_msg = format!(
                     "Unexpected character '{}' at line {}, column {}",
@@ -560,12 +810,13 @@ impl<'a> Lexer<'a> {
     fn tokenize_all_positioned(&mut self) -> Result<Vec<PositionedToken>, String> {
         let mut tokens = Vec::new();
         loop {
-            // Capture position before tokenizing (start of token)
+            // Capture position at the start of the token, past any whitespace
+            self.skip_whitespace();
             let line = self.line;
             let column = self.column;
             let token = self.next_token()?;
             let is_eof = matches!(token, Token::Eof);
-            tokens.push(PositionedToken::new(token, line, column));
+            tokens.push(PositionedToken::new(token, line, column).with_end(self.line, self.column));
             if is_eof {
                 break;
             }
@@ -595,7 +846,7 @@ impl<'a> Lexer<'a> {
 ///
 /// let source = "let x: i32 = 42;";
 /// let tokens = tokenize(source).unwrap();
-/// // tokens: [Let, Ident("x"), Colon, Ident("i32"), Equal, Number(42.0), Semicolon, Eof]
+/// // tokens: [Let, Ident("x"), Colon, Ident("i32"), Equal, Int(42), Semicolon, Eof]
 /// ```
 ///
 /// # Errors
@@ -680,6 +931,183 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tokenize_for_in_range() {
+        let tokens = tokenize("for i in 0..n").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::For,
+                Token::Ident("i".to_string()),
+                Token::In,
+                Token::Int(0),
+                Token::DotDot,
+                Token::Ident("n".to_string()),
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_tuple_index() {
+        // `0.1` after a dot is two tuple indexes, not a float
+        let tokens = tokenize("pair.0.1 + 2.5").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Ident("pair".to_string()),
+                Token::Dot,
+                Token::Int(0),
+                Token::Dot,
+                Token::Int(1),
+                Token::Plus,
+                Token::Float(2.5),
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_loop_control_and_labels() {
+        let tokens = tokenize("'outer: loop { break 'outer; continue; }").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Label("outer".to_string()),
+                Token::Colon,
+                Token::Loop,
+                Token::LBrace,
+                Token::Break,
+                Token::Label("outer".to_string()),
+                Token::Semicolon,
+                Token::Continue,
+                Token::Semicolon,
+                Token::RBrace,
+                Token::Eof
+            ]
+        );
+
+        let err = tokenize("break ' outer;").unwrap_err();
+        assert!(err.contains("Error[E001]"), "{}", err);
+        assert!(err.contains("loop label"), "{}", err);
+    }
+
+    #[test]
+    fn test_tokenize_cast() {
+        let tokens = tokenize("x as i32").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Ident("x".to_string()),
+                Token::As,
+                Token::Ident("i32".to_string()),
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_use() {
+        let tokens = tokenize(r#"use "res://lib/combat.ferris" as fx;"#).unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Use,
+                Token::StringLit("res://lib/combat.ferris".to_string()),
+                Token::As,
+                Token::Ident("fx".to_string()),
+                Token::Semicolon,
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_const() {
+        let tokens = tokenize("const MAX: i32 = 10;").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Const,
+                Token::Ident("MAX".to_string()),
+                Token::Colon,
+                Token::Ident("i32".to_string()),
+                Token::Equal,
+                Token::Int(10),
+                Token::Semicolon,
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_array_brackets() {
+        let tokens = tokenize("[1, 2][0]").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LBracket,
+                Token::Int(1),
+                Token::Comma,
+                Token::Int(2),
+                Token::RBracket,
+                Token::LBracket,
+                Token::Int(0),
+                Token::RBracket,
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_enum_and_match() {
+        let tokens = tokenize("enum match => = ==").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Enum,
+                Token::Match,
+                Token::FatArrow,
+                Token::Equal,
+                Token::EqualEqual,
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_struct_impl_and_self_receivers() {
+        let tokens = tokenize("struct impl (&self) (&mut self)").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Struct,
+                Token::Impl,
+                Token::LParen,
+                Token::Amp,
+                Token::Ident("self".to_string()),
+                Token::RParen,
+                Token::LParen,
+                Token::Amp,
+                Token::Mut,
+                Token::Ident("self".to_string()),
+                Token::RParen,
+                Token::Eof
+            ]
+        );
+
+        // Elsewhere '&' is bitwise AND
+        assert_eq!(
+            tokenize("a & selfish").unwrap(),
+            vec![
+                Token::Ident("a".to_string()),
+                Token::Amp,
+                Token::Ident("selfish".to_string()),
+                Token::Eof
+            ]
+        );
+    }
+
     #[test]
     fn test_tokenize_identifiers() {
         let tokens = tokenize("foo bar _ready _process self").unwrap();
@@ -737,6 +1165,21 @@ mod tests {
         assert_eq!(tokens, vec![Token::At, Token::Eof]);
     }
 
+    #[test]
+    fn test_tokenize_question_mark() {
+        let tokens = tokenize("load()?").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Ident("load".to_string()),
+                Token::LParen,
+                Token::RParen,
+                Token::Question,
+                Token::Eof
+            ]
+        );
+    }
+
     #[test]
     fn test_tokenize_export_keyword() {
         let tokens = tokenize("export").unwrap();
@@ -756,10 +1199,38 @@ mod tests {
         assert_eq!(
             tokens,
             vec![
-                Token::Number(42.0),
-                Token::Number(3.5),
-                Token::Number(0.5),
-                Token::Number(100.0),
+                Token::Int(42),
+                Token::Float(3.5),
+                Token::Float(0.5),
+                Token::Float(100.0),
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_token_positions_skip_whitespace_and_comments() {
+        let source = "fn f() {\n    // comment\n    return  x;\n}";
+        let tokens = tokenize_positioned(source).unwrap();
+        let position = |token: &Token| {
+            let pt = tokens.iter().find(|pt| &pt.token == token).unwrap();
+            ((pt.line, pt.column), (pt.end_line, pt.end_column))
+        };
+        assert_eq!(position(&Token::Fn), ((1, 1), (1, 3)));
+        assert_eq!(position(&Token::Return), ((3, 5), (3, 11)));
+        assert_eq!(position(&Token::Ident("x".to_string())), ((3, 13), (3, 14)));
+    }
+
+    #[test]
+    fn test_tokenize_integers_exactly() {
+        // 16777217 is not representable as f32 and must not be rounded
+        let tokens = tokenize("16777217 2147483647 9007199254740993").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Int(16777217),
+                Token::Int(2147483647),
+                Token::Int(9007199254740993),
                 Token::Eof
             ]
         );
@@ -793,6 +1264,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tokenize_interpolated_string() {
+        let source = r#"let s = "HP: {hp + 1}/{{max}}";"#;
+        let tokens = tokenize_positioned(source).unwrap();
+        let parts = match &tokens[3].token {
+            Token::InterpolatedString(parts) => parts,
+            other => panic!("Expected interpolated string, got {:?}", other),
+        };
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0], StringPart::Text("HP: ".to_string()));
+        assert_eq!(parts[2], StringPart::Text("/{max}".to_string()));
+
+        // Embedded tokens keep their position in the source
+        let StringPart::Expr(inner) = &parts[1] else {
+            panic!("Expected interpolated expression, got {:?}", parts[1]);
+        };
+        let inner: Vec<_> = inner
+            .iter()
+            .map(|pt| (pt.token.clone(), pt.line, pt.column))
+            .collect();
+        assert_eq!(
+            inner,
+            vec![
+                (Token::Ident("hp".to_string()), 1, 15),
+                (Token::Plus, 1, 18),
+                (Token::Int(1), 1, 20),
+                (Token::Eof, 1, 21),
+            ]
+        );
+
+        // Strings without interpolation, and lone closing braces, stay plain
+        assert_eq!(
+            tokenize(r#""{{}} }""#).unwrap(),
+            vec![Token::StringLit("{} }".to_string()), Token::Eof]
+        );
+    }
+
+    #[test]
+    fn test_interpolation_errors() {
+        let err = tokenize(r#""HP: {}""#).unwrap_err();
+        assert!(
+            err.contains("E007") && err.contains("Empty interpolation"),
+            "{}",
+            err
+        );
+
+        let err = tokenize(r#""HP: {hp" + x"#).unwrap_err();
+        assert!(
+            err.contains("E007") && err.contains("Unterminated interpolation"),
+            "{}",
+            err
+        );
+
+        let err = tokenize(r#""HP: {hp"#).unwrap_err();
+        assert!(
+            err.contains("E002") && err.contains("Unterminated string"),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn test_tokenize_operators() {
         let tokens = tokenize("+ - * / = == != < <= > >= && || !").unwrap();
@@ -894,7 +1426,7 @@ mod tests {
                 Token::Dot,
                 Token::Ident("x".to_string()),
                 Token::PlusEqual,
-                Token::Number(50.0),
+                Token::Float(50.0),
                 Token::Star,
                 Token::Ident("delta".to_string()),
                 Token::Semicolon,
@@ -926,8 +1458,8 @@ fn _process(delta: f32) {
         assert!(tokens.contains(&Token::If));
         assert!(tokens.contains(&Token::Fn));
         assert!(tokens.contains(&Token::Ident("dir".to_string())));
-        assert!(tokens.contains(&Token::Number(1.0)));
-        assert!(tokens.contains(&Token::Number(100.0)));
+        assert!(tokens.contains(&Token::Float(1.0)));
+        assert!(tokens.contains(&Token::Float(100.0)));
         assert!(tokens.contains(&Token::Greater));
         assert!(tokens.contains(&Token::Less));
         assert!(tokens.last().unwrap() == &Token::Eof);
@@ -952,7 +1484,7 @@ fn test() {
                 Token::Let,
                 Token::Ident("x".to_string()),
                 Token::Equal,
-                Token::Number(5.0),
+                Token::Int(5),
                 Token::Semicolon,
                 Token::RBrace,
                 Token::Eof
@@ -992,38 +1524,59 @@ fn test() {
 
     #[test]
     fn test_error_unexpected_character() {
-        let result = tokenize("~"); // Changed from @ to ~
+        let result = tokenize("$"); // Changed from ~ to $ since ~ is now bitwise NOT
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Unexpected character"));
     }
 
     #[test]
-    fn test_error_single_ampersand() {
-        let result = tokenize("a & b");
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        // Error now includes context, check for key parts
-        assert!(error.contains("Unexpected character '&'"));
-        assert!(error.contains("&&"));
-        assert!(error.contains("logical AND"));
+    fn test_tokenize_arithmetic_and_bitwise_operators() {
+        let tokens = tokenize("% & | ^ ~ << >> && ||").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Percent,
+                Token::Amp,
+                Token::Pipe,
+                Token::Caret,
+                Token::Tilde,
+                Token::Shl,
+                Token::Shr,
+                Token::And,
+                Token::Or,
+                Token::Eof
+            ]
+        );
     }
 
     #[test]
-    fn test_error_single_pipe() {
-        let result = tokenize("a | b");
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        // Error now includes context, check for key parts
-        assert!(error.contains("Unexpected character '|'"));
-        assert!(error.contains("||"));
-        assert!(error.contains("logical OR"));
+    fn test_tokenize_compound_assignment_operators() {
+        let tokens = tokenize("+= -= *= /= %= &= |= ^= <<= >>= <= >=").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::PlusEqual,
+                Token::MinusEqual,
+                Token::StarEqual,
+                Token::SlashEqual,
+                Token::PercentEqual,
+                Token::AmpEqual,
+                Token::PipeEqual,
+                Token::CaretEqual,
+                Token::ShlEqual,
+                Token::ShrEqual,
+                Token::LessEqual,
+                Token::GreaterEqual,
+                Token::Eof
+            ]
+        );
     }
 
     #[test]
     fn test_token_name() {
         assert_eq!(Token::Fn.name(), "fn");
         assert_eq!(Token::Ident("foo".to_string()).name(), "identifier");
-        assert_eq!(Token::Number(42.0).name(), "number");
+        assert_eq!(Token::Int(42).name(), "number");
         assert_eq!(Token::StringLit("test".to_string()).name(), "string");
         assert_eq!(Token::EqualEqual.name(), "==");
     }
@@ -1044,33 +1597,30 @@ fn test() {
 
     #[test]
     fn test_edge_case_large_number_max() {
-        // Test parsing very large numbers (approaching i64::MAX when converted)
+        // Integer literals are carried exactly up to i64::MAX
         let input = "9223372036854775807"; // i64::MAX
         let tokens = tokenize(input).unwrap();
-        assert_eq!(tokens.len(), 2); // Number + EOF
-        match &tokens[0] {
-            Token::Number(n) => {
-                assert!(*n > 0.0, "Large number should be positive");
-                assert!(n.is_finite(), "Large number should be finite");
-            }
-            _ => panic!("Expected Number token"),
-        }
+        assert_eq!(tokens.len(), 2); // Int + EOF
+        assert_eq!(tokens[0], Token::Int(i64::MAX));
     }
 
     #[test]
     fn test_edge_case_large_number_negative() {
-        // Test parsing very large negative numbers
-        let input = "-9223372036854775808"; // i64::MIN (as negative literal)
-        let tokens = tokenize(input).unwrap();
-        assert_eq!(tokens.len(), 3); // Minus + Number + EOF
+        // The literal part of i64::MIN does not fit in i64, so it is rejected
+        // rather than silently rounded through a float
+        let input = "-9223372036854775808";
+        let result = tokenize(input);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .contains("Invalid number '9223372036854775808'")
+        );
+
+        let tokens = tokenize("-9223372036854775807").unwrap();
+        assert_eq!(tokens.len(), 3); // Minus + Int + EOF
         assert_eq!(tokens[0], Token::Minus);
-        match &tokens[1] {
-            Token::Number(n) => {
-                assert!(*n > 0.0, "Number part should be positive");
-                assert!(n.is_finite(), "Large number should be finite");
-            }
-            _ => panic!("Expected Number token"),
-        }
+        assert_eq!(tokens[1], Token::Int(i64::MAX));
     }
 
     #[test]
@@ -1080,12 +1630,12 @@ fn test() {
         let tokens = tokenize(input).unwrap();
         assert_eq!(tokens.len(), 2);
         match &tokens[0] {
-            Token::Number(n) => {
+            Token::Float(n) => {
                 assert!(*n > 0.0, "Small number should be positive");
                 assert!(*n < 0.001, "Number should be very small");
                 assert!(n.is_finite(), "Small number should be finite");
             }
-            _ => panic!("Expected Number token"),
+            _ => panic!("Expected Float token"),
         }
     }
 
@@ -1155,12 +1705,12 @@ fn test() {
         // Test multiple dots (could be confused with field access or range)
         let input = "x...y";
         let tokens = tokenize(input).unwrap();
-        // Should tokenize as: x, ., ., ., y
-        assert!(tokens.len() >= 5);
+        // Should tokenize as: x, .., ., y (range operator is matched greedily)
+        assert!(tokens.len() >= 4);
         assert_eq!(tokens[0], Token::Ident("x".to_string()));
-        assert_eq!(tokens[1], Token::Dot);
+        assert_eq!(tokens[1], Token::DotDot);
         assert_eq!(tokens[2], Token::Dot);
-        assert_eq!(tokens[3], Token::Dot);
+        assert_eq!(tokens[3], Token::Ident("y".to_string()));
     }
 
     #[test]
@@ -1231,12 +1781,9 @@ fn test() {
         // Test number exceeding i32::MAX
         let input = "let x = 2147483648;"; // i32::MAX + 1
         let result = tokenize(input);
-        assert!(result.is_ok()); // Lexer uses f32, should succeed
+        assert!(result.is_ok()); // Fits in i64, range is checked by the type checker
         let tokens = result.unwrap();
-        match &tokens[3] {
-            Token::Number(n) => assert!(*n == 2147483648.0),
-            _ => panic!("Expected Number"),
-        }
+        assert_eq!(tokens[3], Token::Int(2147483648));
     }
 
     #[test]
@@ -1266,8 +1813,8 @@ fn test() {
 
     #[test]
     fn test_lexer_invalid_character_at() {
-        // Test ~ character (invalid)
-        let input = "let ~ = 5;";
+        // Test $ character (invalid)
+        let input = "let $ = 5;";
         let result = tokenize(input);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Unexpected character"));
@@ -1331,8 +1878,8 @@ fn test() {
         assert!(result.is_ok());
         let tokens = result.unwrap();
         match &tokens[3] {
-            Token::Number(n) => assert_eq!(*n, 123.0),
-            _ => panic!("Expected Number"),
+            Token::Int(n) => assert_eq!(*n, 123),
+            _ => panic!("Expected Int"),
         }
     }
 
@@ -1345,8 +1892,8 @@ fn test() {
         let tokens = result.unwrap();
         // Should parse as: let, x, =, 5., ;, EOF
         match &tokens[3] {
-            Token::Number(_) => {}
-            _ => panic!("Expected Number"),
+            Token::Float(n) => assert_eq!(*n, 5.0),
+            _ => panic!("Expected Float"),
         }
     }
 
@@ -1448,8 +1995,8 @@ fn test() {
         let tokens = result.unwrap();
         assert_eq!(tokens[3], Token::Minus);
         match &tokens[4] {
-            Token::Number(n) => assert_eq!(*n, 5.0),
-            _ => panic!("Expected Number"),
+            Token::Int(n) => assert_eq!(*n, 5),
+            _ => panic!("Expected Int"),
         }
     }
 
@@ -1677,8 +2224,8 @@ fn test() {
         let tokens = result.unwrap();
         // Currently: let, x, =, 1, _000_000, ;, EOF
         match &tokens[3] {
-            Token::Number(n) => assert_eq!(*n, 1.0), // Just "1"
-            _ => panic!("Expected Number token for '1'"),
+            Token::Int(n) => assert_eq!(*n, 1), // Just "1"
+            _ => panic!("Expected Int token for '1'"),
         }
     }
 
@@ -1697,8 +2244,8 @@ fn test() {
         );
         let tokens = result.unwrap();
         match &tokens[3] {
-            Token::Number(n) => assert_eq!(*n, 0.0), // Just "0"
-            _ => panic!("Expected Number token for '0'"),
+            Token::Int(n) => assert_eq!(*n, 0), // Just "0"
+            _ => panic!("Expected Int token for '0'"),
         }
     }
 
@@ -1717,8 +2264,8 @@ fn test() {
         );
         let tokens = result.unwrap();
         match &tokens[3] {
-            Token::Number(n) => assert_eq!(*n, 0.0), // Just "0"
-            _ => panic!("Expected Number token for '0'"),
+            Token::Int(n) => assert_eq!(*n, 0), // Just "0"
+            _ => panic!("Expected Int token for '0'"),
         }
     }
 
@@ -1794,8 +2341,8 @@ fn test() {
     #[test]
     fn test_lexer_deeply_nested_operators() {
         // Test long chain of operators (stress test token buffer)
-        // Removed % as it may not be supported
-        let input = "a + b - c * d / e && g || h == i != j < k > l <= m >= n";
+        let input =
+            "a + b - c * d / e % f && g || h == i != j < k > l <= m >= n & o | p ^ q << r >> s";
         let result = tokenize(input);
 
         // Should handle many operators
@@ -1919,7 +2466,7 @@ fn test() {
         // Behavior depends on language design:
         if let Ok(tokens) = result {
             // If .5 is valid number literal
-            assert_eq!(tokens[3], Token::Dot); // Or Token::Number if supported
+            assert_eq!(tokens[3], Token::Dot); // Or Token::Float if supported
         } else {
             // If .5 not supported (parse as dot + number)
         }
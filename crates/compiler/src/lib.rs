@@ -31,17 +31,21 @@
 //! # Modules
 //!
 //! - [`ast`]: Abstract Syntax Tree node definitions
+//! - [`const_eval`]: Compile-time evaluation of constant expressions
 //! - [`error_code`]: Error code definitions and categories
 //! - [`error_context`]: Error formatting with source context
 //! - [`lexer`]: Lexical analysis (tokenization)
+//! - [`module`]: `use` declarations and module loaders for multi-file scripts
 //! - [`parser`]: Syntax analysis (AST generation)
 //! - [`span`]: Source code location tracking for error messages and LSP
 //! - [`type_checker`]: Semantic analysis (type checking)
 
 pub mod ast;
+pub mod const_eval;
 pub mod error_code;
 pub mod error_context;
 pub mod lexer;
+pub mod module;
 pub mod parser;
 pub mod span;
 pub mod suggestions;
@@ -86,6 +90,7 @@ pub mod type_checker;
 /// - Source contains invalid tokens (lexer errors)
 /// - Syntax is malformed (parser errors)
 /// - Types are incompatible (type checker errors)
+/// - The script uses a module (use [`compile_with_loader`] for those)
 ///
 /// Error messages include:
 /// - Line and column numbers
@@ -93,14 +98,45 @@ pub mod type_checker;
 /// - Visual pointer to error location
 /// - Helpful hint about the issue
 pub fn compile(source: &str) -> Result<ast::Program, String> {
-    let positioned_tokens = lexer::tokenize_positioned(source)?;
-    let mut ast = parser::parse_positioned(&positioned_tokens, source)?;
-
-    // Type check and extract property metadata (Phase 5)
-    let metadata = type_checker::check_and_extract_metadata(&ast, source)?;
-    ast.property_metadata = metadata;
+    compile_with_loader(source, "", &module::MemoryLoader::new())
+}
 
-    Ok(ast)
+/// Compile the script at `path` together with the modules it uses.
+///
+/// Modules named by `use` declarations are resolved against `path` and read
+/// through `loader`. The returned program contains the items of every module
+/// under their qualified names (e.g. `combat.damage`), and its `files` hold
+/// the script followed by each module, indexed by the `file` of each span.
+///
+/// # Examples
+///
+/// ```
+/// use ferrisscript_compiler::compile_with_loader;
+/// use ferrisscript_compiler::module::MemoryLoader;
+///
+/// let loader = MemoryLoader::new()
+///     .with_file("res://lib/stats.ferris", "const MAX_HP: i32 = 100;");
+/// let source = r#"
+///     use "res://lib/stats.ferris";
+///
+///     fn full() -> i32 { return stats.MAX_HP; }
+/// "#;
+/// let program = compile_with_loader(source, "res://player.ferris", &loader).unwrap();
+/// assert_eq!(program.files.len(), 2);
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` for the same errors as [`compile`], reported against the file
+/// they occur in (errors inside a module start with the module's path), and
+/// when a module cannot be loaded, modules use each other in a cycle, or a
+/// module declares a global variable or signal.
+pub fn compile_with_loader(
+    source: &str,
+    path: &str,
+    loader: &dyn module::ModuleLoader,
+) -> Result<ast::Program, String> {
+    module::compile_program(source, path, loader)
 }
 
 #[cfg(test)]
@@ -151,6 +187,36 @@ mod tests {
         assert!(compile(&source).is_ok());
     }
 
+    #[test]
+    fn test_compile_collections() {
+        let source = std::fs::read_to_string(example_path("collections.ferris")).unwrap();
+        let result = compile(&source);
+        if let Err(e) = &result {
+            eprintln!("collections.ferris error: {}", e);
+        }
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_compile_match() {
+        let source = std::fs::read_to_string(example_path("match.ferris")).unwrap();
+        let result = compile(&source);
+        if let Err(e) = &result {
+            eprintln!("match.ferris error: {}", e);
+        }
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_compile_structs() {
+        let source = std::fs::read_to_string(example_path("structs.ferris")).unwrap();
+        let result = compile(&source);
+        if let Err(e) = &result {
+            eprintln!("structs.ferris error: {}", e);
+        }
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_compile_functions() {
         let source = std::fs::read_to_string(example_path("functions.ferris")).unwrap();
@@ -0,0 +1,957 @@
+//! Multi-file programs: `use` declarations and the loaders that find modules.
+//!
+//! A script names another file with `use "res://lib/combat.ferris";` and then
+//! reaches that module's functions, constants, enums and structs through the
+//! module's name: `combat.damage(10)`, `combat.MAX_HP`, `combat.Element.Fire`.
+//! Modules are compiled into the script that uses them. Every item of a module
+//! is renamed to its qualified name (`combat.damage`), so the type checker and
+//! the runtime see a single program.
+//!
+//! Files are read through a [`ModuleLoader`]: [`FileSystemLoader`] reads them
+//! from disk, mapping `res://` to a project directory, and [`MemoryLoader`]
+//! serves them from memory. Each module is compiled once however many files use
+//! it, circular `use` is an error, and every span records the file it is in so
+//! that errors inside a module name the module's file.
+//!
+//! # Examples
+//!
+//! ```
+//! use ferrisscript_compiler::compile_with_loader;
+//! use ferrisscript_compiler::module::MemoryLoader;
+//!
+//! let loader = MemoryLoader::new().with_file(
+//!     "res://lib/combat.ferris",
+//!     "fn damage(base: i32) -> i32 { return base * 2; }",
+//! );
+//! let source = r#"
+//!     use "lib/combat.ferris";
+//!
+//!     fn hit() -> i32 { return combat.damage(5); }
+//! "#;
+//! let program = compile_with_loader(source, "res://main.ferris", &loader).unwrap();
+//! assert!(program.functions.iter().any(|f| f.name == "combat.damage"));
+//! ```
+
+use crate::ast::*;
+use crate::error_code::ErrorCode;
+use crate::error_context::format_error_with_code;
+use crate::lexer::{self, Token};
+use crate::parser;
+use crate::span::FileId;
+use crate::type_checker;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Finds and reads the files named by `use` declarations.
+pub trait ModuleLoader {
+    /// Read the module at `path`, a path returned by [`resolve`](Self::resolve),
+    /// or describe why it cannot be read
+    fn load(&self, path: &str) -> Result<String, String>;
+
+    /// The path of the module that `path` names in a `use` declaration of the
+    /// file at `importer`
+    fn resolve(&self, importer: &str, path: &str) -> String {
+        resolve_path(importer, path)
+    }
+}
+
+/// Reads modules from disk. `res://` paths are read from the project directory
+/// given to [`FileSystemLoader::new`], as are other relative paths.
+#[derive(Debug, Clone)]
+pub struct FileSystemLoader {
+    root: PathBuf,
+}
+
+impl FileSystemLoader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FileSystemLoader { root: root.into() }
+    }
+}
+
+impl ModuleLoader for FileSystemLoader {
+    fn load(&self, path: &str) -> Result<String, String> {
+        let relative = path.strip_prefix("res://").unwrap_or(path);
+        std::fs::read_to_string(self.root.join(relative)).map_err(|e| e.to_string())
+    }
+}
+
+/// Serves modules from memory, for tests and tools that already hold the sources.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryLoader {
+    files: HashMap<String, String>,
+}
+
+impl MemoryLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, replacing any earlier file with the same path
+    pub fn insert(&mut self, path: impl Into<String>, source: impl Into<String>) {
+        self.files.insert(path.into(), source.into());
+    }
+
+    /// Builder form of [`insert`](Self::insert)
+    pub fn with_file(mut self, path: impl Into<String>, source: impl Into<String>) -> Self {
+        self.insert(path, source);
+        self
+    }
+}
+
+impl ModuleLoader for MemoryLoader {
+    fn load(&self, path: &str) -> Result<String, String> {
+        self.files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| "no such file".to_string())
+    }
+}
+
+/// Resolve a `use` path against the file that contains the declaration.
+///
+/// `res://` and `/` paths are absolute; other paths start from the importing
+/// file's directory. `.` and `..` segments are removed.
+///
+/// # Examples
+///
+/// ```
+/// use ferrisscript_compiler::module::resolve_path;
+///
+/// assert_eq!(
+///     resolve_path("res://scenes/player.ferris", "../lib/combat.ferris"),
+///     "res://lib/combat.ferris"
+/// );
+/// assert_eq!(
+///     resolve_path("res://scenes/player.ferris", "res://lib/combat.ferris"),
+///     "res://lib/combat.ferris"
+/// );
+/// ```
+pub fn resolve_path(importer: &str, path: &str) -> String {
+    if path.starts_with("res://") || path.starts_with('/') {
+        return normalize(path);
+    }
+    let directory = importer.rfind('/').map_or("", |end| &importer[..=end]);
+    normalize(&format!("{}{}", directory, path))
+}
+
+/// Remove `.` and `..` segments and repeated slashes from a path
+fn normalize(path: &str) -> String {
+    let (root, rest) = if let Some(rest) = path.strip_prefix("res://") {
+        ("res://", rest)
+    } else if let Some(rest) = path.strip_prefix('/') {
+        ("/", rest)
+    } else {
+        ("", path)
+    };
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in rest.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    format!("{}{}", root, segments.join("/"))
+}
+
+/// The name a module is used by when its `use` declaration has no `as`: the
+/// file name without its extension.
+///
+/// # Examples
+///
+/// ```
+/// use ferrisscript_compiler::module::module_name;
+///
+/// assert_eq!(module_name("res://lib/combat.ferris"), "combat");
+/// ```
+pub fn module_name(path: &str) -> String {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+    stem.to_string()
+}
+
+/// Whether `name` can name a module, i.e. is an identifier and not a keyword
+pub fn is_module_name(name: &str) -> bool {
+    matches!(lexer::tokenize(name).as_deref(), Ok([Token::Ident(ident), Token::Eof]) if ident == name)
+}
+
+/// A file of the program being compiled
+struct Module {
+    path: String,
+    /// Prefix of the module's items in the linked program (empty for the
+    /// script, whose items keep their names)
+    name: String,
+    source: String,
+    program: Program,
+    /// Names bound by the module's `use` declarations, mapped to the prefixes
+    /// of the modules they name
+    uses: HashMap<String, String>,
+}
+
+/// Loads a script's modules and links them into one program
+struct Linker<'a> {
+    loader: &'a dyn ModuleLoader,
+    /// Every file loaded so far, indexed by its `FileId`; the script comes first
+    modules: Vec<Module>,
+    /// Modules whose `use` declarations are being loaded, outermost first
+    loading: Vec<usize>,
+    /// Modules in the order they finished loading, so each comes after the
+    /// modules it uses and the script comes last
+    order: Vec<usize>,
+}
+
+/// Compile the script at `path` together with the modules it uses.
+pub(crate) fn compile_program(
+    source: &str,
+    path: &str,
+    loader: &dyn ModuleLoader,
+) -> Result<Program, String> {
+    let mut linker = Linker {
+        loader,
+        modules: Vec::new(),
+        loading: Vec::new(),
+        order: Vec::new(),
+    };
+    let script = linker.parse(path.to_string(), source.to_string())?;
+    linker.load_uses(script)?;
+    linker.link()
+}
+
+impl Linker<'_> {
+    /// Parse a file and add it to the program, returning its index
+    fn parse(&mut self, path: String, source: String) -> Result<usize, String> {
+        let index = self.modules.len();
+        let file = FileId(index as u32);
+        let tokens = lexer::tokenize_positioned(&source).map_err(|e| in_file(e, &path, file))?;
+        let program =
+            parser::parse_file(&tokens, &source, file).map_err(|e| in_file(e, &path, file))?;
+
+        let name = if index == 0 {
+            String::new()
+        } else {
+            let base = module_name(&path);
+            let mut name = base.clone();
+            let mut n = 1;
+            while self.modules.iter().any(|module| module.name == name) {
+                n += 1;
+                name = format!("{}_{}", base, n);
+            }
+            name
+        };
+        self.modules.push(Module {
+            path,
+            name,
+            source,
+            program,
+            uses: HashMap::new(),
+        });
+
+        if index > 0 {
+            self.check_module_items(index)?;
+        }
+        Ok(index)
+    }
+
+    /// Modules are libraries: they have no state and cannot be attached to a node
+    fn check_module_items(&self, index: usize) -> Result<(), String> {
+        let program = &self.modules[index].program;
+        let item = program
+            .global_vars
+            .iter()
+            .map(|var| ("global variable", var.span))
+            .chain(program.signals.iter().map(|signal| ("signal", signal.span)))
+            .next();
+        match item {
+            Some((kind, span)) => Err(self.error_at(
+                index,
+                ErrorCode::E982,
+                &format!("Modules cannot declare a {}", kind),
+                span,
+                "Modules may only declare functions, constants, enums, structs and impl blocks",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Load the modules a file uses, and the modules those use in turn
+    fn load_uses(&mut self, index: usize) -> Result<(), String> {
+        self.loading.push(index);
+        let uses = self.modules[index].program.uses.clone();
+        for use_decl in &uses {
+            let path = self
+                .loader
+                .resolve(&self.modules[index].path, &use_decl.path);
+            let target = match self.modules.iter().position(|module| module.path == path) {
+                Some(target) if self.loading.contains(&target) => {
+                    let start = self.loading.iter().position(|&i| i == target).unwrap_or(0);
+                    let mut chain: Vec<&str> = self.loading[start..]
+                        .iter()
+                        .map(|&i| self.modules[i].path.as_str())
+                        .collect();
+                    chain.push(&path);
+                    return Err(self.error_at(
+                        index,
+                        ErrorCode::E981,
+                        &format!("Module '{}' uses itself: {}", path, chain.join(" -> ")),
+                        use_decl.span,
+                        "Move the items both modules need into a module that uses neither",
+                    ));
+                }
+                Some(target) => target,
+                None => {
+                    let source = self.loader.load(&path).map_err(|reason| {
+                        self.error_at(
+                            index,
+                            ErrorCode::E980,
+                            &format!("Cannot load module '{}': {}", path, reason),
+                            use_decl.span,
+                            "Relative module paths start from the directory of this file",
+                        )
+                    })?;
+                    let target = self.parse(path, source)?;
+                    self.load_uses(target)?;
+                    target
+                }
+            };
+            let prefix = self.modules[target].name.clone();
+            self.modules[index]
+                .uses
+                .insert(use_decl.name.clone(), prefix);
+        }
+        self.loading.pop();
+        self.order.push(index);
+        Ok(())
+    }
+
+    /// Merge the modules into one program, type checking each against its own
+    /// source after the modules it uses
+    fn link(self) -> Result<Program, String> {
+        let mut linked = Program::new();
+        for &index in &self.order {
+            let module = &self.modules[index];
+            let program = qualify(module);
+            linked.consts.extend(program.consts);
+            linked.global_vars.extend(program.global_vars);
+            linked.signals.extend(program.signals);
+            linked.enums.extend(program.enums);
+            linked.structs.extend(program.structs);
+            linked.impls.extend(program.impls);
+            linked.functions.extend(program.functions);
+            linked.uses = program.uses;
+
+            linked.property_metadata =
+                type_checker::check_and_extract_metadata(&linked, &module.source)
+                    .map_err(|e| in_file(e, &module.path, FileId(index as u32)))?;
+        }
+        linked.files = self
+            .modules
+            .into_iter()
+            .map(|module| SourceFile {
+                path: module.path,
+                source: module.source,
+            })
+            .collect();
+        Ok(linked)
+    }
+
+    /// Format an error at `span` in the given file
+    fn error_at(
+        &self,
+        index: usize,
+        code: ErrorCode,
+        message: &str,
+        span: Span,
+        hint: &str,
+    ) -> String {
+        let module = &self.modules[index];
+        let (line, column) = (span.line(), span.column());
+        let base_msg = format!("{} at line {}, column {}", message, line, column);
+        in_file(
+            format_error_with_code(code, &base_msg, &module.source, line, column, hint),
+            &module.path,
+            span.file,
+        )
+    }
+}
+
+/// Name the module's file at the start of each error it reported. Errors in the
+/// script itself are left as they are.
+fn in_file(errors: String, path: &str, file: FileId) -> String {
+    if file == FileId::MAIN {
+        return errors;
+    }
+    errors
+        .split('\n')
+        .map(|line| {
+            if line.starts_with("Error[") {
+                format!("{}: {}", path, line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The module's program with its items renamed to their qualified names and
+/// the names bound by its `use` declarations replaced by the modules' prefixes
+fn qualify(module: &Module) -> Program {
+    let mut program = module.program.clone();
+    let mut types = HashSet::new();
+    let mut values = HashSet::new();
+    if !module.name.is_empty() {
+        types.extend(program.enums.iter().map(|e| e.name.clone()));
+        types.extend(program.structs.iter().map(|s| s.name.clone()));
+        values.extend(types.iter().cloned());
+        values.extend(program.consts.iter().map(|c| c.name.clone()));
+        values.extend(program.functions.iter().map(|f| f.name.clone()));
+    }
+    let mut qualifier = Qualifier {
+        prefix: &module.name,
+        types,
+        values,
+        uses: &module.uses,
+        scopes: Vec::new(),
+    };
+
+    for const_decl in &mut program.consts {
+        qualifier.declare(&mut const_decl.name);
+        const_decl.ty = qualifier.ty(&const_decl.ty);
+        qualifier.expr(&mut const_decl.value);
+    }
+    for var in &mut program.global_vars {
+        if let Some(ty) = &mut var.ty {
+            *ty = qualifier.ty(ty);
+        }
+        qualifier.expr(&mut var.value);
+    }
+    for signal in &mut program.signals {
+        for (_, ty) in &mut signal.parameters {
+            *ty = qualifier.ty(ty);
+        }
+    }
+    for enum_decl in &mut program.enums {
+        qualifier.declare(&mut enum_decl.name);
+    }
+    for struct_decl in &mut program.structs {
+        qualifier.declare(&mut struct_decl.name);
+        for field in &mut struct_decl.fields {
+            field.ty = qualifier.ty(&field.ty);
+        }
+    }
+    for impl_block in &mut program.impls {
+        qualifier.type_name(&mut impl_block.type_name);
+        for method in &mut impl_block.methods {
+            qualifier.function(&mut method.function, method.self_param.is_some());
+        }
+    }
+    for function in &mut program.functions {
+        qualifier.declare(&mut function.name);
+        qualifier.function(function, false);
+    }
+    program
+}
+
+/// Rewrites the names in one module's declarations
+struct Qualifier<'a> {
+    prefix: &'a str,
+    /// The module's enums and structs
+    types: HashSet<String>,
+    /// The module's functions, constants, enums and structs
+    values: HashSet<String>,
+    uses: &'a HashMap<String, String>,
+    /// Local variables, innermost scope last; they hide the module's items
+    scopes: Vec<HashSet<String>>,
+}
+
+impl Qualifier<'_> {
+    /// The linked name of `name`, if it is one of the module's items (`own`) or
+    /// an item of a module it uses
+    fn qualified(&self, name: &str, own: &HashSet<String>) -> Option<String> {
+        match name.split_once('.') {
+            Some((module, item)) => self
+                .uses
+                .get(module)
+                .map(|prefix| format!("{}.{}", prefix, item)),
+            None if own.contains(name) => Some(format!("{}.{}", self.prefix, name)),
+            None => None,
+        }
+    }
+
+    /// Rename a declaration of the module
+    fn declare(&self, name: &mut String) {
+        if !self.prefix.is_empty() {
+            *name = format!("{}.{}", self.prefix, name);
+        }
+    }
+
+    fn type_name(&self, name: &mut String) {
+        if let Some(qualified) = self.qualified(name, &self.types) {
+            *name = qualified;
+        }
+    }
+
+    fn value(&self, name: &mut String) {
+        let local = self
+            .scopes
+            .iter()
+            .any(|scope| scope.contains(name.as_str()));
+        if !local && let Some(qualified) = self.qualified(name, &self.values) {
+            *name = qualified;
+        }
+    }
+
+    /// Rename the type names inside a type such as `Array<Enemy>` or `(Dir, i32)`
+    fn ty(&self, ty: &str) -> String {
+        let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+        let mut qualified = String::new();
+        let mut rest = ty;
+        while let Some(start) = rest.find(is_name_char) {
+            qualified.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+            let name = &rest[..end];
+            match self.qualified(name, &self.types) {
+                Some(name) => qualified.push_str(&name),
+                None => qualified.push_str(name),
+            }
+            rest = &rest[end..];
+        }
+        qualified.push_str(rest);
+        qualified
+    }
+
+    fn bind(&mut self, name: &str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string());
+        }
+    }
+
+    fn function(&mut self, function: &mut Function, has_self: bool) {
+        self.scopes.push(HashSet::new());
+        if has_self {
+            self.bind("self");
+        }
+        for param in &mut function.params {
+            param.ty = self.ty(&param.ty);
+            self.bind(&param.name);
+        }
+        if let Some(return_type) = &mut function.return_type {
+            *return_type = self.ty(return_type);
+        }
+        self.block(&mut function.body);
+        self.scopes.pop();
+    }
+
+    fn block(&mut self, stmts: &mut [Stmt]) {
+        self.scopes.push(HashSet::new());
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+        self.scopes.pop();
+    }
+
+    fn stmt(&mut self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::Expr(expr) => self.expr(expr),
+            Stmt::Let {
+                name,
+                pattern,
+                ty,
+                value,
+                ..
+            } => {
+                self.expr(value);
+                if let Some(ty) = ty {
+                    *ty = self.ty(ty);
+                }
+                match pattern {
+                    Some(LetPattern::Tuple(names)) => {
+                        for name in names {
+                            self.bind(name);
+                        }
+                    }
+                    Some(LetPattern::Struct { type_name, fields }) => {
+                        self.type_name(type_name);
+                        for (_, name) in fields {
+                            self.bind(name);
+                        }
+                    }
+                    None => self.bind(name),
+                }
+            }
+            Stmt::Assign { target, value, .. } => {
+                self.expr(target);
+                self.expr(value);
+            }
+            Stmt::If {
+                cond,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.expr(cond);
+                self.block(then_branch);
+                self.block(else_branch);
+            }
+            Stmt::While { cond, body, .. } => {
+                self.expr(cond);
+                self.block(body);
+            }
+            Stmt::For {
+                var,
+                value_var,
+                iterable,
+                body,
+                ..
+            } => {
+                self.expr(iterable);
+                self.scopes.push(HashSet::new());
+                self.bind(var);
+                if let Some(value_var) = value_var {
+                    self.bind(value_var);
+                }
+                self.block(body);
+                self.scopes.pop();
+            }
+            Stmt::Loop { body, .. } => self.block(body),
+            Stmt::Break { .. } | Stmt::Continue { .. } => {}
+            Stmt::Return { value, .. } => {
+                if let Some(value) = value {
+                    self.expr(value);
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Literal(..) => {}
+            Expr::Variable(name, _) => self.value(name),
+            Expr::Binary(left, _, right, _)
+            | Expr::Assign(left, right, _)
+            | Expr::CompoundAssign(left, _, right, _)
+            | Expr::Index(left, right, _)
+            | Expr::Range(left, right, _) => {
+                self.expr(left);
+                self.expr(right);
+            }
+            Expr::Unary(_, inner, _) | Expr::FieldAccess(inner, _, _) | Expr::Try(inner, _) => {
+                self.expr(inner)
+            }
+            Expr::Cast(inner, ty, _) => {
+                self.expr(inner);
+                *ty = self.ty(ty);
+            }
+            Expr::Call(name, args, _) => {
+                self.value(name);
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+            Expr::StructLiteral {
+                type_name, fields, ..
+            } => {
+                self.type_name(type_name);
+                for (_, value) in fields {
+                    self.expr(value);
+                }
+            }
+            Expr::ArrayLiteral(elements, _) | Expr::Tuple(elements, _) => {
+                for element in elements {
+                    self.expr(element);
+                }
+            }
+            Expr::DictLiteral(entries, _) => {
+                for (key, value) in entries {
+                    self.expr(key);
+                    self.expr(value);
+                }
+            }
+            Expr::Match {
+                scrutinee, arms, ..
+            } => {
+                self.expr(scrutinee);
+                for arm in arms {
+                    self.scopes.push(HashSet::new());
+                    match &mut arm.pattern {
+                        Pattern::EnumVariant { enum_name, .. } => self.type_name(enum_name),
+                        Pattern::Some { binding, .. }
+                        | Pattern::Ok { binding, .. }
+                        | Pattern::Err { binding, .. } => self.bind(binding),
+                        Pattern::Wildcard(_) | Pattern::Literal(..) | Pattern::None(_) => {}
+                    }
+                    for stmt in &mut arm.body {
+                        self.stmt(stmt);
+                    }
+                    if let Some(value) = &mut arm.value {
+                        self.expr(value);
+                    }
+                    self.scopes.pop();
+                }
+            }
+            Expr::MethodCall { receiver, args, .. } => {
+                self.expr(receiver);
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+            Expr::If {
+                cond,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.expr(cond);
+                self.expr(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.expr(else_branch);
+                }
+            }
+            Expr::Block { stmts, value, .. } => {
+                self.scopes.push(HashSet::new());
+                for stmt in stmts {
+                    self.stmt(stmt);
+                }
+                if let Some(value) = value {
+                    self.expr(value);
+                }
+                self.scopes.pop();
+            }
+            Expr::Lambda { params, body, .. } => {
+                self.scopes.push(HashSet::new());
+                for (name, ty) in params {
+                    if let Some(ty) = ty {
+                        *ty = self.ty(ty);
+                    }
+                    self.bind(name);
+                }
+                self.expr(body);
+                self.scopes.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compile_with_loader;
+
+    const COMBAT: &str = r#"
+        use "math.ferris";
+
+        const CRIT: i32 = 2;
+
+        enum Element { Fire, Ice }
+
+        struct Hit { amount: i32, element: Element }
+
+        impl Hit {
+            fn new(amount: i32) -> Hit {
+                return Hit { amount: amount, element: Element.Fire };
+            }
+        }
+
+        fn damage(hit: Hit) -> i32 {
+            let bonus = match hit.element { Element.Fire => CRIT, Element.Ice => 0 };
+            return math.clamp(hit.amount * bonus, 0, 100);
+        }
+    "#;
+
+    const MATH: &str =
+        "fn clamp(x: i32, low: i32, high: i32) -> i32 { return min(max(x, low), high); }";
+
+    fn loader() -> MemoryLoader {
+        MemoryLoader::new()
+            .with_file("res://lib/combat.ferris", COMBAT)
+            .with_file("res://lib/math.ferris", MATH)
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        assert_eq!(
+            resolve_path("res://main.ferris", "lib/combat.ferris"),
+            "res://lib/combat.ferris"
+        );
+        assert_eq!(
+            resolve_path("res://a/b/c.ferris", "./../../lib//x.ferris"),
+            "res://lib/x.ferris"
+        );
+        assert_eq!(
+            resolve_path("res://a/b.ferris", "/tmp/x.ferris"),
+            "/tmp/x.ferris"
+        );
+        assert_eq!(resolve_path("", "../x.ferris"), "../x.ferris");
+        assert_eq!(
+            resolve_path("scripts/main.ferris", "x.ferris"),
+            "scripts/x.ferris"
+        );
+    }
+
+    #[test]
+    fn test_module_names() {
+        assert_eq!(module_name("res://lib/combat.ferris"), "combat");
+        assert_eq!(module_name("utils"), "utils");
+        assert_eq!(module_name("res://lib/my-utils.ferris"), "my-utils");
+        assert!(is_module_name("combat"));
+        assert!(is_module_name("_shared2"));
+        assert!(!is_module_name("my-utils"));
+        assert!(!is_module_name("match"));
+        assert!(!is_module_name(""));
+    }
+
+    #[test]
+    fn test_modules_are_qualified_and_linked() {
+        let source = r#"
+            use "lib/combat.ferris";
+
+            fn strike(amount: i32) -> i32 {
+                let hit: combat.Hit = combat.Hit.new(amount);
+                let combat.Hit { amount: base } = hit;
+                return combat.damage(hit) + base * combat.CRIT;
+            }
+        "#;
+        let program = compile_with_loader(source, "res://main.ferris", &loader())
+            .unwrap_or_else(|e| panic!("{}", e));
+        let names: Vec<&str> = program.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["math.clamp", "combat.damage", "strike"]);
+        assert_eq!(program.consts[0].name, "combat.CRIT");
+        assert_eq!(program.enums[0].name, "combat.Element");
+        assert_eq!(program.structs[0].name, "combat.Hit");
+        assert_eq!(program.structs[0].fields[1].ty, "combat.Element");
+        assert_eq!(program.impls[0].type_name, "combat.Hit");
+
+        let files: Vec<&str> = program.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            files,
+            [
+                "res://main.ferris",
+                "res://lib/combat.ferris",
+                "res://lib/math.ferris"
+            ]
+        );
+        assert_eq!(program.functions[0].span.file, FileId(2));
+        assert_eq!(program.functions[2].span.file, FileId::MAIN);
+    }
+
+    #[test]
+    fn test_alias_and_shared_module() {
+        let loader = loader().with_file(
+            "res://lib/ui.ferris",
+            "use \"math.ferris\" as m;\nfn percent(x: i32) -> i32 { return m.clamp(x, 0, 100); }",
+        );
+        let source = r#"
+            use "res://lib/ui.ferris";
+            use "res://lib/math.ferris" as numbers;
+
+            fn show(x: i32) -> i32 { return ui.percent(numbers.clamp(x, -5, 500)); }
+        "#;
+        let program = compile_with_loader(source, "res://main.ferris", &loader).unwrap();
+        // math is compiled once, under its own name, though both files use it
+        let names: Vec<&str> = program.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["math.clamp", "ui.percent", "show"]);
+    }
+
+    #[test]
+    fn test_locals_hide_module_items() {
+        let loader = MemoryLoader::new().with_file(
+            "res://scale.ferris",
+            r#"
+            const FACTOR: i32 = 3;
+            fn apply(x: i32) -> i32 {
+                let scale = |v: i32| v * FACTOR;
+                return scale(x);
+            }
+            fn scale(x: i32) -> i32 {
+                let FACTOR = 10;
+                return x * FACTOR;
+            }
+            "#,
+        );
+        let program =
+            compile_with_loader("use \"scale.ferris\";", "res://main.ferris", &loader).unwrap();
+        let apply = program.functions[0].to_string();
+        assert!(apply.contains("scale(x)"), "{}", apply);
+        assert!(apply.contains("(v * scale.FACTOR)"), "{}", apply);
+        let scale = program.functions[1].to_string();
+        assert!(scale.contains("(x * FACTOR)"), "{}", scale);
+    }
+
+    #[test]
+    fn test_module_errors_name_the_file() {
+        let loader = MemoryLoader::new().with_file(
+            "res://lib/bad.ferris",
+            "fn f() -> i32 {\n    return true;\n}",
+        );
+        let err = compile_with_loader("use \"lib/bad.ferris\";", "res://main.ferris", &loader)
+            .unwrap_err();
+        assert!(
+            err.starts_with("res://lib/bad.ferris: Error[E206]"),
+            "{}",
+            err
+        );
+        assert!(err.contains(" 2 |     return true;"), "{}", err);
+
+        // Errors in the script are reported as before
+        let err = compile_with_loader(
+            "use \"lib/bad.ferris\";\nfn g() -> i32 { return false; }",
+            "res://main.ferris",
+            &MemoryLoader::new().with_file("res://lib/bad.ferris", ""),
+        )
+        .unwrap_err();
+        assert!(err.starts_with("Error[E206]"), "{}", err);
+    }
+
+    #[test]
+    fn test_module_load_errors() {
+        let err = compile_with_loader("use \"missing.ferris\";", "res://main.ferris", &loader())
+            .unwrap_err();
+        assert!(err.contains("Error[E980]"), "{}", err);
+        assert!(err.contains("res://missing.ferris"), "{}", err);
+
+        let cycle = MemoryLoader::new()
+            .with_file("res://a.ferris", "use \"b.ferris\";")
+            .with_file("res://b.ferris", "use \"a.ferris\";");
+        let err =
+            compile_with_loader("use \"a.ferris\";", "res://main.ferris", &cycle).unwrap_err();
+        assert!(err.contains("Error[E981]"), "{}", err);
+        assert!(
+            err.contains("res://a.ferris -> res://b.ferris -> res://a.ferris"),
+            "{}",
+            err
+        );
+
+        let stateful = MemoryLoader::new()
+            .with_file("res://state.ferris", "fn f() {}\nlet mut count: i32 = 0;");
+        let err = compile_with_loader("use \"state.ferris\";", "res://main.ferris", &stateful)
+            .unwrap_err();
+        assert!(
+            err.starts_with("res://state.ferris: Error[E982]"),
+            "{}",
+            err
+        );
+        assert!(err.contains("global variable"), "{}", err);
+    }
+
+    #[test]
+    fn test_file_system_loader() {
+        let root = std::env::temp_dir().join(format!("ferris_modules_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("lib")).unwrap();
+        std::fs::write(root.join("lib/stats.ferris"), "const MAX_HP: i32 = 100;").unwrap();
+
+        let loader = FileSystemLoader::new(&root);
+        assert_eq!(
+            loader.load("res://lib/stats.ferris").unwrap(),
+            "const MAX_HP: i32 = 100;"
+        );
+        assert!(loader.load("res://lib/nope.ferris").is_err());
+        let source = "use \"lib/stats.ferris\";\nfn hp() -> i32 { return stats.MAX_HP; }";
+        assert!(compile_with_loader(source, "res://main.ferris", &loader).is_ok());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
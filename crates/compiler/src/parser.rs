@@ -8,10 +8,11 @@
 //!
 //! The parser supports:
 //! - Function definitions with parameters and return types
-//! - Global variable declarations (let and let mut)
+//! - Constant declarations (`const`) and global variable declarations (let and let mut)
 //! - Control flow (if/else, while, return)
 //! - Binary and unary expressions with proper precedence
-//! - Function calls and member access
+//! - Function calls, method calls and member access
+//! - Struct declarations and `impl` blocks
 //!
 //! # Performance
 //!
@@ -31,10 +32,12 @@
 //! ```
 
 use crate::ast::*;
+use crate::const_eval::{self, ConstValue};
 use crate::error_code::ErrorCode;
 use crate::error_context::format_error_with_code;
-use crate::lexer::{PositionedToken, Token};
-use crate::span::{Position, Span};
+use crate::lexer::{PositionedToken, StringPart, Token};
+use crate::span::{FileId, Position, Span};
+use std::collections::{HashMap, HashSet};
 
 pub struct Parser<'a> {
     tokens: Vec<PositionedToken>,
@@ -45,6 +48,12 @@ pub struct Parser<'a> {
     // Error recovery fields (Phase 3C)
     panic_mode: bool,    // Track if currently recovering from error
     errors: Vec<String>, // Collect all errors during parsing
+    // Values of the constants declared so far, for property hint bounds
+    constants: HashMap<String, ConstValue>,
+    // Namespaces bound by the `use` declarations parsed so far
+    modules: HashSet<String>,
+    // File the spans of the parsed nodes belong to
+    file: FileId,
 }
 
 impl<'a> Parser<'a> {
@@ -57,6 +66,9 @@ impl<'a> Parser<'a> {
             current_column: 1,
             panic_mode: false,
             errors: Vec::new(),
+            constants: HashMap::new(),
+            modules: HashSet::new(),
+            file: FileId::MAIN,
         }
     }
 
@@ -97,7 +109,7 @@ impl<'a> Parser<'a> {
 
     fn expect(&mut self, expected: Token) -> Result<Token, String> {
         let current = self.current();
-        let (line, column) = self.current_position();
+        let (line, column) = self.previous_end();
         if std::mem::discriminant(current) == std::mem::discriminant(&expected) {
             Ok(self.advance())
         } else {
@@ -119,11 +131,25 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Zero-length span at the start of the current token, where the construct
+    /// being parsed begins
     fn span(&self) -> Span {
         // TODO(v0.0.5): Track actual byte offsets during parsing
         // For now, use offset 0 (unknown) and create zero-length spans
-        let pos = Position::new(self.current_line, self.current_column, 0);
-        Span::point(pos)
+        let (line, column) = self.current_position();
+        Span::point(Position::new(line, column, 0)).in_file(self.file)
+    }
+
+    /// Position just past the last consumed token, where a missing token belongs
+    fn previous_end(&self) -> (usize, usize) {
+        match self
+            .position
+            .checked_sub(1)
+            .and_then(|i| self.tokens.get(i))
+        {
+            Some(pt) => (pt.end_line, pt.end_column),
+            None => self.current_position(),
+        }
     }
 
     /// Create a span from a start position to the current position.
@@ -145,7 +171,7 @@ impl<'a> Parser<'a> {
         // For now, use offset 0 (unknown)
         let start_pos = Position::new(start_line, start_column, 0);
         let end_pos = Position::new(self.current_line, self.current_column, 0);
-        Span::new(start_pos, end_pos)
+        Span::new(start_pos, end_pos).in_file(self.file)
     }
 
     /// Synchronize parser to next safe recovery point after error.
@@ -175,7 +201,14 @@ impl<'a> Parser<'a> {
 
             // Check if current token is a safe recovery point
             match self.current() {
-                Token::Fn | Token::Let | Token::RBrace => {
+                Token::Fn
+                | Token::Use
+                | Token::Let
+                | Token::Const
+                | Token::Enum
+                | Token::Struct
+                | Token::Impl
+                | Token::RBrace => {
                     self.panic_mode = false;
                     return;
                 }
@@ -221,8 +254,17 @@ impl<'a> Parser<'a> {
         let mut program = Program::new();
 
         while !matches!(self.current(), Token::Eof) {
+            if matches!(self.current(), Token::Use) {
+                match self.parse_use_declaration() {
+                    Ok(use_decl) => program.uses.push(use_decl),
+                    Err(e) => {
+                        self.record_error(e);
+                        self.synchronize();
+                        // Continue parsing to find more errors
+                    }
+                }
             // Check if it's a global let statement (with or without @export)
-            if matches!(self.current(), Token::Let | Token::At) {
+            } else if matches!(self.current(), Token::Let | Token::At) {
                 match self.parse_global_var() {
                     Ok(global_var) => program.global_vars.push(global_var),
                     Err(e) => {
@@ -231,6 +273,15 @@ impl<'a> Parser<'a> {
                         // Continue parsing to find more errors
                     }
                 }
+            } else if matches!(self.current(), Token::Const) {
+                match self.parse_const_declaration() {
+                    Ok(const_decl) => program.consts.push(const_decl),
+                    Err(e) => {
+                        self.record_error(e);
+                        self.synchronize();
+                        // Continue parsing to find more errors
+                    }
+                }
             } else if matches!(self.current(), Token::Signal) {
                 match self.parse_signal_declaration() {
                     Ok(signal) => program.signals.push(signal),
@@ -240,6 +291,33 @@ impl<'a> Parser<'a> {
                         // Continue parsing to find more errors
                     }
                 }
+            } else if matches!(self.current(), Token::Enum) {
+                match self.parse_enum_declaration() {
+                    Ok(enum_decl) => program.enums.push(enum_decl),
+                    Err(e) => {
+                        self.record_error(e);
+                        self.synchronize();
+                        // Continue parsing to find more errors
+                    }
+                }
+            } else if matches!(self.current(), Token::Struct) {
+                match self.parse_struct_declaration() {
+                    Ok(struct_decl) => program.structs.push(struct_decl),
+                    Err(e) => {
+                        self.record_error(e);
+                        self.synchronize();
+                        // Continue parsing to find more errors
+                    }
+                }
+            } else if matches!(self.current(), Token::Impl) {
+                match self.parse_impl_block() {
+                    Ok(impl_block) => program.impls.push(impl_block),
+                    Err(e) => {
+                        self.record_error(e);
+                        self.synchronize();
+                        // Continue parsing to find more errors
+                    }
+                }
             } else if matches!(self.current(), Token::Fn) {
                 match self.parse_function() {
                     Ok(function) => program.functions.push(function),
@@ -262,7 +340,7 @@ impl<'a> Parser<'a> {
                     self.source,
                     self.current_line,
                     self.current_column,
-                    "Only function or global variable declarations allowed at top level",
+                    "Only use, function, constant, global variable, signal, enum, struct, or impl declarations allowed at top level",
                 );
                 self.record_error(error);
                 // Advance at least one token to prevent infinite loop
@@ -280,6 +358,104 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parse `use "path";` or `use "path" as name;`
+    fn parse_use_declaration(&mut self) -> Result<UseDecl, String> {
+        let span = self.span();
+        self.expect(Token::Use)?;
+
+        let path = match self.advance() {
+            Token::StringLit(path) => path,
+            t => {
+                let base_msg = format!(
+                    "Expected module path after 'use', found {} at line {}, column {}",
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                return Err(format_error_with_code(
+                    ErrorCode::E100,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Module paths are strings, e.g. use \"res://lib/combat.ferris\";",
+                ));
+            }
+        };
+
+        let alias = matches!(self.current(), Token::As);
+        let name = if alias {
+            self.advance();
+            match self.advance() {
+                Token::Ident(name) => name,
+                t => {
+                    let base_msg = format!(
+                        "Expected module name after 'as', found {} at line {}, column {}",
+                        t.name(),
+                        self.current_line,
+                        self.current_column
+                    );
+                    return Err(format_error_with_code(
+                        ErrorCode::E109,
+                        &base_msg,
+                        self.source,
+                        self.current_line,
+                        self.current_column,
+                        "Module name must be an identifier",
+                    ));
+                }
+            }
+        } else {
+            crate::module::module_name(&path)
+        };
+
+        let problem = if !crate::module::is_module_name(&name) {
+            Some(format!(
+                "Module path '{}' does not give a valid module name",
+                path
+            ))
+        } else if self.modules.contains(&name) {
+            Some(format!("Module name '{}' is already in use", name))
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            let (line, column) = (span.line(), span.column());
+            let base_msg = format!("{} at line {}, column {}", problem, line, column);
+            return Err(format_error_with_code(
+                ErrorCode::E983,
+                &base_msg,
+                self.source,
+                line,
+                column,
+                "Name the module with 'as', e.g. use \"res://lib/combat.ferris\" as fx;",
+            ));
+        }
+
+        self.expect(Token::Semicolon)?;
+        self.modules.insert(name.clone());
+        Ok(UseDecl {
+            path,
+            name,
+            alias,
+            span,
+        })
+    }
+
+    /// Extend a module name bound by `use` with the item after it, so that
+    /// `combat.damage` is read as the single name `combat.damage`
+    fn qualified_name(&mut self, name: String) -> String {
+        if !self.modules.contains(&name) || !matches!(self.current(), Token::Dot) {
+            return name;
+        }
+        let Token::Ident(item) = self.peek(1).clone() else {
+            return name;
+        };
+        self.advance();
+        self.advance();
+        format!("{}.{}", name, item)
+    }
+
     /// Parse @export annotation with optional property hints
     ///
     /// Supports:
@@ -300,9 +476,13 @@ impl<'a> Parser<'a> {
         let hint = if matches!(self.current(), Token::LParen) {
             self.advance(); // consume '('
 
-            // Parse hint type (identifier)
-            if let Token::Ident(hint_type) = self.current() {
-                let hint_name = hint_type.clone();
+            // Parse hint type (identifier; `enum` is also a keyword)
+            let hint_name = match self.current() {
+                Token::Ident(hint_type) => Some(hint_type.clone()),
+                Token::Enum => Some("enum".to_string()),
+                _ => None,
+            };
+            if let Some(hint_name) = hint_name {
                 self.advance();
 
                 match hint_name.as_str() {
@@ -420,34 +600,140 @@ impl<'a> Parser<'a> {
         Ok(Some(ExportAnnotation { hint, span }))
     }
 
-    /// Helper to parse a numeric literal for property hints
+    /// Helper to parse a numeric property hint value: a number or a constant
+    /// expression over numbers and previously declared constants
     fn parse_number(&mut self, context: &str) -> Result<f32, String> {
-        match self.current() {
-            Token::Number(val) => {
-                let num = *val;
+        let found = self.current().name().to_string();
+        self.parse_expression(0)
+            .ok()
+            .and_then(|expr| const_eval::eval(&expr, &self.constants).ok())
+            .and_then(|value| value.to_f64())
+            .map(|n| n as f32)
+            .ok_or_else(|| format!("Expected number for {}, found {}", context, found))
+    }
+
+    /// Parse optional generic arguments following a type name (e.g. the `<i32>`
+    /// in `Array<i32>`) and return the full type name as stored in the AST.
+    fn parse_type_arguments(&mut self, base: String) -> Result<String, String> {
+        let base = self.qualified_name(base);
+        if !matches!(self.current(), Token::Less) {
+            return Ok(base);
+        }
+        self.advance();
+
+        let mut args = Vec::new();
+        loop {
+            let arg = match self.advance() {
+                Token::Ident(name) => self.parse_type_arguments(name)?,
+                Token::Fn => self.parse_function_type()?,
+                Token::LParen => self.parse_tuple_type()?,
+                t => {
+                    let base_msg = format!(
+                        "Expected type argument for '{}', found {} at line {}, column {}",
+                        base,
+                        t.name(),
+                        self.current_line,
+                        self.current_column
+                    );
+                    return Err(format_error_with_code(
+                        ErrorCode::E110,
+                        &base_msg,
+                        self.source,
+                        self.current_line,
+                        self.current_column,
+                        "Type arguments must be type names (e.g., Array<i32>)",
+                    ));
+                }
+            };
+            args.push(arg);
+
+            if matches!(self.current(), Token::Comma) {
                 self.advance();
-                Ok(num)
+            } else {
+                break;
             }
-            Token::Minus => {
+        }
+
+        // `>>` closes two argument lists at once (`Array<Array<i32>>`): consume
+        // its first `>` here and leave the second for the enclosing list
+        if matches!(self.current(), Token::Shr) {
+            let closing = &mut self.tokens[self.position];
+            closing.token = Token::Greater;
+            closing.column += 1;
+        } else {
+            self.expect(Token::Greater)?;
+        }
+        Ok(format!("{}<{}>", base, args.join(", ")))
+    }
+
+    /// Parse a function type after its `fn` keyword (e.g. the `(i32) -> bool` in
+    /// `fn(i32) -> bool`) and return it as stored in the AST. Without `->` the
+    /// function returns nothing.
+    fn parse_function_type(&mut self) -> Result<String, String> {
+        self.expect(Token::LParen)?;
+        let mut params = Vec::new();
+        while !matches!(self.current(), Token::RParen) {
+            params.push(self.parse_type("parameter")?);
+            if matches!(self.current(), Token::Comma) {
                 self.advance();
-                match self.current() {
-                    Token::Number(val) => {
-                        let num = -*val;
-                        self.advance();
-                        Ok(num)
-                    }
-                    _ => Err(format!(
-                        "Expected number for {}, found {}",
-                        context,
-                        self.current().name()
-                    )),
-                }
+            } else {
+                break;
+            }
+        }
+        self.expect(Token::RParen)?;
+
+        let params = params.join(", ");
+        if matches!(self.current(), Token::Minus) && matches!(self.peek(1), Token::Greater) {
+            self.advance();
+            self.advance();
+            let return_type = self.parse_type("return")?;
+            Ok(format!("fn({}) -> {}", params, return_type))
+        } else {
+            Ok(format!("fn({})", params))
+        }
+    }
+
+    /// Parse a tuple type after its `(` (e.g. the `f32, Vector2)` in
+    /// `(f32, Vector2)`) and return it as stored in the AST
+    fn parse_tuple_type(&mut self) -> Result<String, String> {
+        let mut elements = vec![self.parse_type("tuple element")?];
+        self.expect(Token::Comma)?;
+        elements.push(self.parse_type("tuple element")?);
+        while matches!(self.current(), Token::Comma) {
+            self.advance();
+            if matches!(self.current(), Token::RParen) {
+                break;
+            }
+            elements.push(self.parse_type("tuple element")?);
+        }
+        self.expect(Token::RParen)?;
+        Ok(format!("({})", elements.join(", ")))
+    }
+
+    /// Parse a type: a name with optional type arguments, a function type or a
+    /// tuple type
+    fn parse_type(&mut self, what: &str) -> Result<String, String> {
+        match self.advance() {
+            Token::Ident(t) => self.parse_type_arguments(t),
+            Token::Fn => self.parse_function_type(),
+            Token::LParen => self.parse_tuple_type(),
+            t => {
+                let base_msg = format!(
+                    "Expected {} type, found {} at line {}, column {}",
+                    what,
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                Err(format_error_with_code(
+                    ErrorCode::E110,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Types are names such as i32 or Array<i32>, function types such as fn(i32) -> bool, or tuples such as (f32, Vector2)",
+                ))
             }
-            _ => Err(format!(
-                "Expected number for {}, found {}",
-                context,
-                self.current().name()
-            )),
         }
     }
 
@@ -489,7 +775,9 @@ impl<'a> Parser<'a> {
         let ty = if matches!(self.current(), Token::Colon) {
             self.advance();
             match self.advance() {
-                Token::Ident(t) => Some(t),
+                Token::Ident(t) => Some(self.parse_type_arguments(t)?),
+                Token::Fn => Some(self.parse_function_type()?),
+                Token::LParen => Some(self.parse_tuple_type()?),
                 t => {
                     let base_msg = format!(
                         "Expected type, found {} at line {}, column {}",
@@ -525,6 +813,85 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_const_declaration(&mut self) -> Result<ConstDecl, String> {
+        let span = self.span();
+        self.expect(Token::Const)?;
+
+        let name = match self.advance() {
+            Token::Ident(n) => n,
+            t => {
+                let base_msg = format!(
+                    "Expected identifier after 'const', found {} at line {}, column {}",
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                return Err(format_error_with_code(
+                    ErrorCode::E109,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Constant name must be an identifier",
+                ));
+            }
+        };
+
+        if !matches!(self.current(), Token::Colon) {
+            let base_msg = format!(
+                "Expected ':' and a type after constant '{}', found {} at line {}, column {}",
+                name,
+                self.current().name(),
+                self.current_line,
+                self.current_column
+            );
+            return Err(format_error_with_code(
+                ErrorCode::E110,
+                &base_msg,
+                self.source,
+                self.current_line,
+                self.current_column,
+                "Constants must declare their type (e.g., const MAX_HP: i32 = 100;)",
+            ));
+        }
+        self.advance();
+        let ty = match self.advance() {
+            Token::Ident(t) => t,
+            t => {
+                let base_msg = format!(
+                    "Expected type, found {} at line {}, column {}",
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                return Err(format_error_with_code(
+                    ErrorCode::E110,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Type annotation must be a valid type name (e.g., i32, f32, bool)",
+                ));
+            }
+        };
+
+        self.expect(Token::Equal)?;
+        let value = self.parse_expression(0)?;
+        self.expect(Token::Semicolon)?;
+
+        // Invalid constants are reported by the type checker
+        if let Ok(folded) = const_eval::eval_as(&value, &ty, &self.constants) {
+            self.constants.insert(name.clone(), folded);
+        }
+
+        Ok(ConstDecl {
+            name,
+            ty,
+            value,
+            span,
+        })
+    }
+
     fn parse_signal_declaration(&mut self) -> Result<Signal, String> {
         let span = self.span();
         self.expect(Token::Signal)?;
@@ -576,7 +943,9 @@ impl<'a> Parser<'a> {
             self.expect(Token::Colon)?;
 
             let param_type = match self.advance() {
-                Token::Ident(t) => t,
+                Token::Ident(t) => self.parse_type_arguments(t)?,
+                Token::Fn => self.parse_function_type()?,
+                Token::LParen => self.parse_tuple_type()?,
                 t => {
                     let base_msg = format!(
                         "Expected type, found {} at line {}, column {}",
@@ -612,15 +981,16 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn parse_function(&mut self) -> Result<Function, String> {
+    /// Parse enum declaration: `enum Name { VariantA, VariantB }`
+    fn parse_enum_declaration(&mut self) -> Result<EnumDecl, String> {
         let span = self.span();
-        self.expect(Token::Fn)?;
+        self.expect(Token::Enum)?;
 
         let name = match self.advance() {
             Token::Ident(n) => n,
             t => {
                 let base_msg = format!(
-                    "Expected function name, found {} at line {}, column {}",
+                    "Expected enum name, found {} at line {}, column {}",
                     t.name(),
                     self.current_line,
                     self.current_column
@@ -631,54 +1001,313 @@ impl<'a> Parser<'a> {
                     self.source,
                     self.current_line,
                     self.current_column,
-                    "Function name must be an identifier",
+                    "Enum name must be an identifier (e.g., enum State { Idle, Running })",
                 ));
             }
         };
 
-        self.expect(Token::LParen)?;
+        self.expect(Token::LBrace)?;
 
-        let mut params = Vec::new();
-        while !matches!(self.current(), Token::RParen) {
-            let param_span = self.span();
-            let param_name = match self.advance() {
-                Token::Ident(n) => n,
+        let mut variants = Vec::new();
+        while !matches!(self.current(), Token::RBrace) {
+            match self.advance() {
+                Token::Ident(variant) => variants.push(variant),
                 t => {
                     let base_msg = format!(
-                        "Expected parameter name, found {} at line {}, column {}",
+                        "Expected enum variant name, found {} at line {}, column {}",
                         t.name(),
                         self.current_line,
                         self.current_column
                     );
                     return Err(format_error_with_code(
-                        ErrorCode::E111,
+                        ErrorCode::E109,
                         &base_msg,
                         self.source,
                         self.current_line,
                         self.current_column,
-                        "Parameter name must be an identifier",
+                        "Enum variants must be identifiers separated by commas",
                     ));
                 }
-            };
+            }
 
-            self.expect(Token::Colon)?;
+            if !matches!(self.current(), Token::RBrace) {
+                self.expect(Token::Comma)?;
+            }
+        }
 
-            let param_type = match self.advance() {
-                Token::Ident(t) => t,
-                t => {
-                    let base_msg = format!(
-                        "Expected parameter type, found {} at line {}, column {}",
-                        t.name(),
-                        self.current_line,
-                        self.current_column
-                    );
-                    return Err(format_error_with_code(
-                        ErrorCode::E111,
-                        &base_msg,
-                        self.source,
-                        self.current_line,
-                        self.current_column,
-                        "Parameter type must be a valid type name (e.g., i32, f32, bool)",
+        self.expect(Token::RBrace)?;
+
+        Ok(EnumDecl {
+            name,
+            variants,
+            span,
+        })
+    }
+
+    /// Parse struct declaration: `struct Name { field: Type, ... }`
+    fn parse_struct_declaration(&mut self) -> Result<StructDecl, String> {
+        let span = self.span();
+        self.expect(Token::Struct)?;
+
+        let name = match self.advance() {
+            Token::Ident(n) => n,
+            t => {
+                let base_msg = format!(
+                    "Expected struct name, found {} at line {}, column {}",
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                return Err(format_error_with_code(
+                    ErrorCode::E109,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Struct name must be an identifier (e.g., struct Enemy { hp: i32 })",
+                ));
+            }
+        };
+
+        self.expect(Token::LBrace)?;
+
+        let mut fields = Vec::new();
+        while !matches!(self.current(), Token::RBrace) {
+            let field_span = self.span();
+            let field_name = match self.advance() {
+                Token::Ident(n) => n,
+                t => {
+                    let base_msg = format!(
+                        "Expected field name, found {} at line {}, column {}",
+                        t.name(),
+                        self.current_line,
+                        self.current_column
+                    );
+                    return Err(format_error_with_code(
+                        ErrorCode::E109,
+                        &base_msg,
+                        self.source,
+                        self.current_line,
+                        self.current_column,
+                        "Struct fields are declared as 'name: Type' separated by commas",
+                    ));
+                }
+            };
+
+            self.expect(Token::Colon)?;
+
+            let field_type = match self.advance() {
+                Token::Ident(t) => self.parse_type_arguments(t)?,
+                Token::Fn => self.parse_function_type()?,
+                Token::LParen => self.parse_tuple_type()?,
+                t => {
+                    let base_msg = format!(
+                        "Expected field type, found {} at line {}, column {}",
+                        t.name(),
+                        self.current_line,
+                        self.current_column
+                    );
+                    return Err(format_error_with_code(
+                        ErrorCode::E110,
+                        &base_msg,
+                        self.source,
+                        self.current_line,
+                        self.current_column,
+                        "Field type must be a valid type name (e.g., i32, f32, Vector2)",
+                    ));
+                }
+            };
+
+            fields.push(StructField {
+                name: field_name,
+                ty: field_type,
+                span: field_span,
+            });
+
+            if !matches!(self.current(), Token::RBrace) {
+                self.expect(Token::Comma)?;
+            }
+        }
+
+        self.expect(Token::RBrace)?;
+
+        Ok(StructDecl { name, fields, span })
+    }
+
+    /// Parse impl block: `impl Name { fn method(&mut self, ...) { ... } ... }`
+    fn parse_impl_block(&mut self) -> Result<ImplBlock, String> {
+        let span = self.span();
+        self.expect(Token::Impl)?;
+
+        let type_name = match self.advance() {
+            Token::Ident(n) => n,
+            t => {
+                let base_msg = format!(
+                    "Expected type name after 'impl', found {} at line {}, column {}",
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                return Err(format_error_with_code(
+                    ErrorCode::E109,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Methods are attached to a declared struct (e.g., impl Enemy { ... })",
+                ));
+            }
+        };
+
+        self.expect(Token::LBrace)?;
+
+        let mut methods = Vec::new();
+        while !matches!(self.current(), Token::RBrace) {
+            methods.push(self.parse_method()?);
+        }
+
+        self.expect(Token::RBrace)?;
+
+        Ok(ImplBlock {
+            type_name,
+            methods,
+            span,
+        })
+    }
+
+    /// Parse a function inside an impl block, with an optional leading
+    /// `&self` or `&mut self` receiver
+    fn parse_method(&mut self) -> Result<Method, String> {
+        let span = self.span();
+        self.expect(Token::Fn)?;
+        let name = self.parse_function_name()?;
+        self.expect(Token::LParen)?;
+
+        let self_param = if matches!(self.current(), Token::Amp) {
+            self.advance();
+            let receiver = if matches!(self.current(), Token::Mut) {
+                self.advance();
+                SelfParam::RefMut
+            } else {
+                SelfParam::Ref
+            };
+
+            match self.advance() {
+                Token::Ident(n) if n == "self" => {}
+                t => {
+                    let base_msg = format!(
+                        "Expected 'self' after '{}', found {} at line {}, column {}",
+                        if receiver == SelfParam::RefMut {
+                            "&mut"
+                        } else {
+                            "&"
+                        },
+                        t.name(),
+                        self.current_line,
+                        self.current_column
+                    );
+                    return Err(format_error_with_code(
+                        ErrorCode::E111,
+                        &base_msg,
+                        self.source,
+                        self.current_line,
+                        self.current_column,
+                        "Method receivers are written '&self' or '&mut self'",
+                    ));
+                }
+            }
+
+            if matches!(self.current(), Token::Comma) {
+                self.advance();
+            }
+            Some(receiver)
+        } else {
+            None
+        };
+
+        let function = self.parse_function_rest(name, span)?;
+        Ok(Method {
+            self_param,
+            function,
+        })
+    }
+
+    fn parse_function(&mut self) -> Result<Function, String> {
+        let span = self.span();
+        self.expect(Token::Fn)?;
+        let name = self.parse_function_name()?;
+        self.expect(Token::LParen)?;
+        self.parse_function_rest(name, span)
+    }
+
+    fn parse_function_name(&mut self) -> Result<String, String> {
+        match self.advance() {
+            Token::Ident(n) => Ok(n),
+            t => {
+                let base_msg = format!(
+                    "Expected function name, found {} at line {}, column {}",
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                Err(format_error_with_code(
+                    ErrorCode::E109,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Function name must be an identifier",
+                ))
+            }
+        }
+    }
+
+    /// Parse the remainder of a function after its opening '(': parameters,
+    /// return type and body
+    fn parse_function_rest(&mut self, name: String, span: Span) -> Result<Function, String> {
+        let mut params = Vec::new();
+        while !matches!(self.current(), Token::RParen) {
+            let param_span = self.span();
+            let param_name = match self.advance() {
+                Token::Ident(n) => n,
+                t => {
+                    let base_msg = format!(
+                        "Expected parameter name, found {} at line {}, column {}",
+                        t.name(),
+                        self.current_line,
+                        self.current_column
+                    );
+                    return Err(format_error_with_code(
+                        ErrorCode::E111,
+                        &base_msg,
+                        self.source,
+                        self.current_line,
+                        self.current_column,
+                        "Parameter name must be an identifier",
+                    ));
+                }
+            };
+
+            self.expect(Token::Colon)?;
+
+            let param_type = match self.advance() {
+                Token::Ident(t) => self.parse_type_arguments(t)?,
+                Token::Fn => self.parse_function_type()?,
+                Token::LParen => self.parse_tuple_type()?,
+                t => {
+                    let base_msg = format!(
+                        "Expected parameter type, found {} at line {}, column {}",
+                        t.name(),
+                        self.current_line,
+                        self.current_column
+                    );
+                    return Err(format_error_with_code(
+                        ErrorCode::E111,
+                        &base_msg,
+                        self.source,
+                        self.current_line,
+                        self.current_column,
+                        "Parameter type must be a valid type name (e.g., i32, f32, bool)",
                     ));
                 }
             };
@@ -703,7 +1332,9 @@ impl<'a> Parser<'a> {
             if matches!(self.current(), Token::Greater) {
                 self.advance();
                 match self.advance() {
-                    Token::Ident(t) => Some(t),
+                    Token::Ident(t) => Some(self.parse_type_arguments(t)?),
+                    Token::Fn => Some(self.parse_function_type()?),
+                    Token::LParen => Some(self.parse_tuple_type()?),
                     t => {
                         let base_msg = format!(
                             "Expected return type, found {} at line {}, column {}",
@@ -762,50 +1393,65 @@ impl<'a> Parser<'a> {
         match self.current() {
             Token::Let | Token::At => self.parse_let_statement(),
             Token::If => self.parse_if_statement(),
-            Token::While => self.parse_while_statement(),
+            Token::While => self.parse_while_statement(None),
+            Token::For => self.parse_for_statement(None),
+            Token::Loop => self.parse_loop_statement(None),
+            Token::Label(_) => self.parse_labeled_loop(),
+            Token::Break | Token::Continue => self.parse_loop_control(),
             Token::Return => self.parse_return_statement(),
+            Token::Match | Token::LBrace => {
+                // Match or block used as a statement: trailing semicolon is optional
+                let expr = if matches!(self.current(), Token::Match) {
+                    self.parse_match_expression()?
+                } else {
+                    self.parse_block_expression()?
+                };
+                if matches!(self.current(), Token::Semicolon) {
+                    self.advance();
+                }
+                Ok(Stmt::Expr(expr))
+            }
             _ => {
                 // Try to parse as expression statement or assignment
                 let expr = self.parse_expression(0)?;
+                self.finish_expression_statement(expr, span)
+            }
+        }
+    }
 
-                // Check for assignment operators
-                match self.current() {
-                    Token::Equal => {
-                        self.advance();
-                        let value = self.parse_expression(0)?;
-                        self.expect(Token::Semicolon)?;
-                        Ok(Stmt::Assign {
-                            target: expr,
-                            value,
-                            span,
-                        })
-                    }
-                    Token::PlusEqual | Token::MinusEqual => {
-                        // Desugar += to = expr + value
-                        let op_token = self.advance();
-                        let rhs = self.parse_expression(0)?;
-                        self.expect(Token::Semicolon)?;
-
-                        let binary_op = match op_token {
-                            Token::PlusEqual => BinaryOp::Add,
-                            Token::MinusEqual => BinaryOp::Sub,
-                            _ => unreachable!(),
-                        };
-
-                        let value =
-                            Expr::Binary(Box::new(expr.clone()), binary_op, Box::new(rhs), span);
-
-                        Ok(Stmt::Assign {
-                            target: expr,
-                            value,
-                            span,
-                        })
-                    }
-                    _ => {
-                        self.expect(Token::Semicolon)?;
-                        Ok(Stmt::Expr(expr))
-                    }
-                }
+    /// Finish an expression statement or assignment whose leading expression
+    /// has already been parsed
+    fn finish_expression_statement(&mut self, expr: Expr, span: Span) -> Result<Stmt, String> {
+        // Check for assignment operators
+        match (self.current(), Self::compound_op(self.current())) {
+            (Token::Equal, _) => {
+                self.advance();
+                let value = self.parse_expression(0)?;
+                self.expect(Token::Semicolon)?;
+                Ok(Stmt::Assign {
+                    target: expr,
+                    value,
+                    span,
+                })
+            }
+            (_, Some(op)) => {
+                // Desugar `x += value` to `x = x + value`
+                self.advance();
+                let rhs = self.parse_expression(0)?;
+                self.expect(Token::Semicolon)?;
+
+                let value =
+                    Expr::Binary(Box::new(expr.clone()), op.binary_op(), Box::new(rhs), span);
+
+                Ok(Stmt::Assign {
+                    target: expr,
+                    value,
+                    span,
+                })
+            }
+            _ => {
+                self.expect(Token::Semicolon)?;
+                Ok(Stmt::Expr(expr))
             }
         }
     }
@@ -825,30 +1471,18 @@ impl<'a> Parser<'a> {
             false
         };
 
-        let name = match self.advance() {
-            Token::Ident(n) => n,
-            t => {
-                let base_msg = format!(
-                    "Expected identifier after 'let', found {} at line {}, column {}",
-                    t.name(),
-                    self.current_line,
-                    self.current_column
-                );
-                return Err(format_error_with_code(
-                    ErrorCode::E109,
-                    &base_msg,
-                    self.source,
-                    self.current_line,
-                    self.current_column,
-                    "Variable name must be an identifier",
-                ));
-            }
+        let (name, pattern) = match self.current() {
+            Token::LParen => (String::new(), Some(self.parse_tuple_pattern()?)),
+            _ if self.at_struct_pattern() => (String::new(), Some(self.parse_struct_pattern()?)),
+            _ => (self.parse_let_name()?, None),
         };
 
         let ty = if matches!(self.current(), Token::Colon) {
             self.advance();
             match self.advance() {
-                Token::Ident(t) => Some(t),
+                Token::Ident(t) => Some(self.parse_type_arguments(t)?),
+                Token::Fn => Some(self.parse_function_type()?),
+                Token::LParen => Some(self.parse_tuple_type()?),
                 t => {
                     let base_msg = format!(
                         "Expected type, found {} at line {}, column {}",
@@ -876,6 +1510,7 @@ impl<'a> Parser<'a> {
 
         Ok(Stmt::Let {
             name,
+            pattern,
             mutable,
             ty,
             value,
@@ -884,14 +1519,102 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn parse_if_statement(&mut self) -> Result<Stmt, String> {
-        let span = self.span();
-        self.expect(Token::If)?;
-
-        let cond = self.parse_expression(0)?;
-        self.expect(Token::LBrace)?;
-
-        let mut then_branch = Vec::new();
+    /// Parse a name bound by a `let` statement or its destructuring pattern
+    fn parse_let_name(&mut self) -> Result<String, String> {
+        match self.advance() {
+            Token::Ident(n) => Ok(n),
+            t => {
+                let base_msg = format!(
+                    "Expected identifier after 'let', found {} at line {}, column {}",
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                Err(format_error_with_code(
+                    ErrorCode::E109,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Variable name must be an identifier",
+                ))
+            }
+        }
+    }
+
+    /// Parse a tuple pattern: `(a, b, ...)` with at least two names
+    fn parse_tuple_pattern(&mut self) -> Result<LetPattern, String> {
+        self.expect(Token::LParen)?;
+        let mut names = vec![self.parse_let_name()?];
+        self.expect(Token::Comma)?;
+        names.push(self.parse_let_name()?);
+        while matches!(self.current(), Token::Comma) {
+            self.advance();
+            if matches!(self.current(), Token::RParen) {
+                break;
+            }
+            names.push(self.parse_let_name()?);
+        }
+        self.expect(Token::RParen)?;
+        Ok(LetPattern::Tuple(names))
+    }
+
+    /// Parse a struct pattern: `TypeName { field, field: name, ... }`
+    /// Whether a struct pattern such as `Enemy { hp }` or
+    /// `combat.Enemy { hp }` starts at the current token
+    fn at_struct_pattern(&self) -> bool {
+        let type_name_at = |offset: usize| {
+            matches!(self.peek(offset), Token::Ident(n) if n.chars().next().is_some_and(|c| c.is_uppercase()))
+                && matches!(self.peek(offset + 1), Token::LBrace)
+        };
+        match self.current() {
+            Token::Ident(n) if self.modules.contains(n) => {
+                matches!(self.peek(1), Token::Dot) && type_name_at(2)
+            }
+            _ => type_name_at(0),
+        }
+    }
+
+    fn parse_struct_pattern(&mut self) -> Result<LetPattern, String> {
+        let type_name = self.parse_let_name()?;
+        let type_name = self.qualified_name(type_name);
+        self.expect(Token::LBrace)?;
+        let mut fields = Vec::new();
+        while !matches!(self.current(), Token::RBrace) {
+            let field = self.parse_let_name()?;
+            let name = if matches!(self.current(), Token::Colon) {
+                self.advance();
+                self.parse_let_name()?
+            } else {
+                field.clone()
+            };
+            fields.push((field, name));
+            if matches!(self.current(), Token::Comma) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        self.expect(Token::RBrace)?;
+        Ok(LetPattern::Struct { type_name, fields })
+    }
+
+    fn parse_if_statement(&mut self) -> Result<Stmt, String> {
+        if matches!(self.peek(1), Token::Let) {
+            let expr = self.parse_if_expression()?;
+            if matches!(self.current(), Token::Semicolon) {
+                self.advance();
+            }
+            return Ok(Stmt::Expr(expr));
+        }
+
+        let span = self.span();
+        self.expect(Token::If)?;
+
+        let cond = self.parse_expression(0)?;
+        self.expect(Token::LBrace)?;
+
+        let mut then_branch = Vec::new();
         while !matches!(self.current(), Token::RBrace) {
             then_branch.push(self.parse_statement()?);
         }
@@ -899,6 +1622,15 @@ impl<'a> Parser<'a> {
 
         let else_branch = if matches!(self.current(), Token::Else) {
             self.advance();
+            if matches!(self.current(), Token::If) {
+                // `else if` nests the next if statement in the else branch
+                return Ok(Stmt::If {
+                    cond,
+                    then_branch,
+                    else_branch: vec![self.parse_if_statement()?],
+                    span,
+                });
+            }
             self.expect(Token::LBrace)?;
             let mut stmts = Vec::new();
             while !matches!(self.current(), Token::RBrace) {
@@ -918,7 +1650,75 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn parse_while_statement(&mut self) -> Result<Stmt, String> {
+    /// Parse `'label: while ...`, `'label: for ...` or `'label: loop ...`
+    fn parse_labeled_loop(&mut self) -> Result<Stmt, String> {
+        let label = match self.advance() {
+            Token::Label(label) => label,
+            _ => unreachable!("parse_labeled_loop called without a label"),
+        };
+        self.expect(Token::Colon)?;
+
+        match self.current() {
+            Token::While => self.parse_while_statement(Some(label)),
+            Token::For => self.parse_for_statement(Some(label)),
+            Token::Loop => self.parse_loop_statement(Some(label)),
+            t => {
+                let base_msg = format!(
+                    "Expected loop after label '{}, found {} at line {}, column {}",
+                    label,
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                Err(format_error_with_code(
+                    ErrorCode::E104,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Only 'while', 'for' and 'loop' can be labeled",
+                ))
+            }
+        }
+    }
+
+    fn parse_loop_statement(&mut self, label: Option<String>) -> Result<Stmt, String> {
+        let span = self.span();
+        self.expect(Token::Loop)?;
+        self.expect(Token::LBrace)?;
+
+        let mut body = Vec::new();
+        while !matches!(self.current(), Token::RBrace) {
+            body.push(self.parse_statement()?);
+        }
+        self.expect(Token::RBrace)?;
+
+        Ok(Stmt::Loop { label, body, span })
+    }
+
+    /// Parse `break;`, `continue;`, `break 'label;` or `continue 'label;`
+    fn parse_loop_control(&mut self) -> Result<Stmt, String> {
+        let span = self.span();
+        let is_break = matches!(self.advance(), Token::Break);
+
+        let label = match self.current() {
+            Token::Label(label) => {
+                let label = label.clone();
+                self.advance();
+                Some(label)
+            }
+            _ => None,
+        };
+        self.expect(Token::Semicolon)?;
+
+        if is_break {
+            Ok(Stmt::Break { label, span })
+        } else {
+            Ok(Stmt::Continue { label, span })
+        }
+    }
+
+    fn parse_while_statement(&mut self, label: Option<String>) -> Result<Stmt, String> {
         let span = self.span();
         self.expect(Token::While)?;
 
@@ -931,7 +1731,81 @@ impl<'a> Parser<'a> {
         }
         self.expect(Token::RBrace)?;
 
-        Ok(Stmt::While { cond, body, span })
+        Ok(Stmt::While {
+            label,
+            cond,
+            body,
+            span,
+        })
+    }
+
+    fn parse_for_statement(&mut self, label: Option<String>) -> Result<Stmt, String> {
+        let span = self.span();
+        self.expect(Token::For)?;
+
+        // `for (key, value) in dict`
+        let (var, value_var) = if matches!(self.current(), Token::LParen) {
+            self.advance();
+            let key = self.parse_loop_variable()?;
+            self.expect(Token::Comma)?;
+            let value = self.parse_loop_variable()?;
+            self.expect(Token::RParen)?;
+            (key, Some(value))
+        } else {
+            (self.parse_loop_variable()?, None)
+        };
+
+        self.expect(Token::In)?;
+
+        let start = self.parse_expression(0)?;
+        let iterable = if matches!(self.current(), Token::DotDot) {
+            self.advance();
+            let end = self.parse_expression(0)?;
+            let range_span = start.span();
+            Expr::Range(Box::new(start), Box::new(end), range_span)
+        } else {
+            start
+        };
+
+        self.expect(Token::LBrace)?;
+
+        let mut body = Vec::new();
+        while !matches!(self.current(), Token::RBrace) {
+            body.push(self.parse_statement()?);
+        }
+        self.expect(Token::RBrace)?;
+
+        Ok(Stmt::For {
+            label,
+            var,
+            value_var,
+            iterable,
+            body,
+            span,
+        })
+    }
+
+    /// Parse a name bound by a `for` loop
+    fn parse_loop_variable(&mut self) -> Result<String, String> {
+        match self.advance() {
+            Token::Ident(n) => Ok(n),
+            t => {
+                let base_msg = format!(
+                    "Expected loop variable after 'for', found {} at line {}, column {}",
+                    t.name(),
+                    self.current_line,
+                    self.current_column
+                );
+                Err(format_error_with_code(
+                    ErrorCode::E109,
+                    &base_msg,
+                    self.source,
+                    self.current_line,
+                    self.current_column,
+                    "Loop variables must be identifiers (e.g., for item in items, or for (key, value) in dict)",
+                ))
+            }
+        }
     }
 
     fn parse_return_statement(&mut self) -> Result<Stmt, String> {
@@ -951,6 +1825,117 @@ impl<'a> Parser<'a> {
 
     // Pratt parser for expressions with operator precedence
     fn parse_expression(&mut self, min_precedence: u8) -> Result<Expr, String> {
+        let mut left = self.parse_cast()?;
+
+        loop {
+            let precedence = self.get_precedence(self.current());
+            if precedence == 0 || precedence < min_precedence {
+                break;
+            }
+
+            let op_token = self.advance();
+            let op = self.token_to_binary_op(&op_token)?;
+
+            let right_precedence = precedence + 1; // Left associative
+            let right = self.parse_expression(right_precedence)?;
+
+            let span = left.span();
+            left = Expr::Binary(Box::new(left), op, Box::new(right), span);
+        }
+
+        Ok(left)
+    }
+
+    /// Desugar an interpolated string into concatenation: `"HP: {hp}"` becomes
+    /// `"HP: " + str(hp)`
+    fn parse_interpolation(&mut self, parts: Vec<StringPart>, span: Span) -> Result<Expr, String> {
+        let mut result: Option<Expr> = None;
+        for part in parts {
+            let piece = match part {
+                StringPart::Text(text) => Expr::Literal(Literal::Str(text), span),
+                StringPart::Expr(tokens) => {
+                    let mut parser = Parser::new(tokens, self.source);
+                    let expr = parser.parse_expression(0)?;
+                    if !matches!(parser.current(), Token::Eof) {
+                        let (line, column) = parser.current_position();
+                        let base_msg = format!(
+                            "Unexpected {} in string interpolation at line {}, column {}",
+                            parser.current().name(),
+                            line,
+                            column
+                        );
+                        return Err(format_error_with_code(
+                            ErrorCode::E101,
+                            &base_msg,
+                            self.source,
+                            line,
+                            column,
+                            "An interpolation holds a single expression, e.g. \"HP: {hp}\"",
+                        ));
+                    }
+                    let expr_span = expr.span();
+                    Expr::Call("str".to_string(), vec![expr], expr_span)
+                }
+            };
+            result = Some(match result {
+                None => piece,
+                Some(left) => Expr::Binary(Box::new(left), BinaryOp::Add, Box::new(piece), span),
+            });
+        }
+        Ok(result.unwrap_or(Expr::Literal(Literal::Str(String::new()), span)))
+    }
+
+    /// Parse `expr as type` casts. `as` binds tighter than binary operators but
+    /// looser than prefix operators, so `-x as i32` casts the negated value.
+    fn parse_cast(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_unary()?;
+
+        while matches!(self.current(), Token::As) {
+            let span = expr.span();
+            self.advance();
+            let ty = match self.advance() {
+                Token::Ident(ty) => ty,
+                t => {
+                    let base_msg = format!(
+                        "Expected type after 'as', found {} at line {}, column {}",
+                        t.name(),
+                        self.current_line,
+                        self.current_column
+                    );
+                    return Err(format_error_with_code(
+                        ErrorCode::E110,
+                        &base_msg,
+                        self.source,
+                        self.current_line,
+                        self.current_column,
+                        "Cast target must be a type name (e.g., x as i32)",
+                    ));
+                }
+            };
+            expr = Expr::Cast(Box::new(expr), ty, span);
+        }
+
+        Ok(expr)
+    }
+
+    /// Parse prefix operators. They bind looser than postfix operators, so
+    /// `!enemy.alive()` negates the call result rather than `enemy`.
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        let span = self.span();
+        let op = match self.current() {
+            Token::Minus => UnaryOp::Neg,
+            Token::Not => UnaryOp::Not,
+            Token::Tilde => UnaryOp::BitNot,
+            _ => return self.parse_postfix(),
+        };
+        self.advance();
+        let expr = self.parse_unary()?;
+        Ok(Expr::Unary(op, Box::new(expr), span))
+    }
+
+    /// Parse a primary expression followed by any field accesses, method
+    /// calls, index operations and `?`.
+    fn parse_postfix(&mut self) -> Result<Expr, String> {
         let mut left = self.parse_primary()?;
 
         loop {
@@ -959,6 +1944,12 @@ impl<'a> Parser<'a> {
                 self.advance();
                 let field = match self.advance() {
                     Token::Ident(name) => name,
+                    // Tuple element: `pair.0`
+                    Token::Int(index) => {
+                        let span = left.span();
+                        left = Expr::FieldAccess(Box::new(left), index.to_string(), span);
+                        continue;
+                    }
                     t => {
                         let base_msg = format!(
                             "Expected field name after '.', found {} at line {}, column {}",
@@ -972,28 +1963,44 @@ impl<'a> Parser<'a> {
                             self.source,
                             self.current_line,
                             self.current_column,
-                            "Field name must be an identifier (e.g., object.field_name)",
+                            "Field name must be an identifier or a tuple index (e.g., object.field_name, pair.0)",
                         ));
                     }
                 };
                 let span = left.span();
-                left = Expr::FieldAccess(Box::new(left), field, span);
+                left = if matches!(self.current(), Token::LParen) {
+                    let args = self.parse_call_arguments()?;
+                    Expr::MethodCall {
+                        receiver: Box::new(left),
+                        method: field,
+                        args,
+                        span,
+                    }
+                } else {
+                    Expr::FieldAccess(Box::new(left), field, span)
+                };
                 continue;
             }
 
-            let precedence = self.get_precedence(self.current());
-            if precedence == 0 || precedence < min_precedence {
-                break;
+            // Handle indexing (same precedence as field access)
+            if matches!(self.current(), Token::LBracket) {
+                self.advance();
+                let index = self.parse_expression(0)?;
+                self.expect(Token::RBracket)?;
+                let span = left.span();
+                left = Expr::Index(Box::new(left), Box::new(index), span);
+                continue;
             }
 
-            let op_token = self.advance();
-            let op = self.token_to_binary_op(&op_token)?;
-
-            let right_precedence = precedence + 1; // Left associative
-            let right = self.parse_expression(right_precedence)?;
+            // Error propagation: `load(path)?`
+            if matches!(self.current(), Token::Question) {
+                self.advance();
+                let span = left.span();
+                left = Expr::Try(Box::new(left), span);
+                continue;
+            }
 
-            let span = left.span();
-            left = Expr::Binary(Box::new(left), op, Box::new(right), span);
+            break;
         }
 
         Ok(left)
@@ -1003,21 +2010,26 @@ impl<'a> Parser<'a> {
         let span = self.span();
 
         match self.current() {
-            Token::Number(n) => {
+            Token::Int(n) => {
                 let num = *n;
                 self.advance();
-                // Check if it's an integer or float
-                if num.fract() == 0.0 && num.abs() < i32::MAX as f32 {
-                    Ok(Expr::Literal(Literal::Int(num as i32), span))
-                } else {
-                    Ok(Expr::Literal(Literal::Float(num), span))
-                }
+                Ok(Expr::Literal(Literal::Int(num), span))
+            }
+            Token::Float(n) => {
+                let num = *n;
+                self.advance();
+                Ok(Expr::Literal(Literal::Float(num), span))
             }
             Token::StringLit(s) => {
                 let string = s.clone();
                 self.advance();
                 Ok(Expr::Literal(Literal::Str(string), span))
             }
+            Token::InterpolatedString(parts) => {
+                let parts = parts.clone();
+                self.advance();
+                self.parse_interpolation(parts, span)
+            }
             Token::True => {
                 self.advance();
                 Ok(Expr::Literal(Literal::Bool(true), span))
@@ -1029,30 +2041,20 @@ impl<'a> Parser<'a> {
             Token::Ident(name) => {
                 let ident = name.clone();
                 self.advance();
+                let ident = self.qualified_name(ident);
 
                 // Check for struct literal: Identifier '{' (only if identifier starts with uppercase)
                 // This prevents parsing `if x { ... }` as a struct literal
+                let type_name = ident.rsplit('.').next().unwrap_or(&ident);
                 if matches!(self.current(), Token::LBrace)
-                    && ident.chars().next().is_some_and(|c| c.is_uppercase())
+                    && type_name.chars().next().is_some_and(|c| c.is_uppercase())
                 {
                     return self.parse_struct_literal(ident, span);
                 }
 
                 // Check for function call
                 if matches!(self.current(), Token::LParen) {
-                    self.advance();
-                    let mut args = Vec::new();
-
-                    while !matches!(self.current(), Token::RParen) {
-                        args.push(self.parse_expression(0)?);
-                        if matches!(self.current(), Token::Comma) {
-                            self.advance();
-                        } else {
-                            break;
-                        }
-                    }
-
-                    self.expect(Token::RParen)?;
+                    let args = self.parse_call_arguments()?;
                     Ok(Expr::Call(ident, args, span))
                 } else {
                     Ok(Expr::Variable(ident, span))
@@ -1061,18 +2063,49 @@ impl<'a> Parser<'a> {
             Token::LParen => {
                 self.advance();
                 let expr = self.parse_expression(0)?;
+                if !matches!(self.current(), Token::Comma) {
+                    self.expect(Token::RParen)?;
+                    return Ok(expr);
+                }
+
+                // A comma makes a tuple of two or more elements: `(a, b)`, `(a, b,)`
+                self.advance();
+                let mut elements = vec![expr, self.parse_expression(0)?];
+                while matches!(self.current(), Token::Comma) {
+                    self.advance();
+                    if matches!(self.current(), Token::RParen) {
+                        break;
+                    }
+                    elements.push(self.parse_expression(0)?);
+                }
                 self.expect(Token::RParen)?;
-                Ok(expr)
+                Ok(Expr::Tuple(elements, span))
             }
-            Token::Minus => {
-                self.advance();
-                let expr = self.parse_primary()?;
-                Ok(Expr::Unary(UnaryOp::Neg, Box::new(expr), span))
+            Token::Match => self.parse_match_expression(),
+            Token::If => self.parse_if_expression(),
+            Token::Pipe | Token::Or => self.parse_lambda(),
+            Token::LBrace => {
+                if self.at_dict_literal() {
+                    self.parse_dict_literal()
+                } else {
+                    self.parse_block_expression()
+                }
             }
-            Token::Not => {
+            Token::LBracket => {
                 self.advance();
-                let expr = self.parse_primary()?;
-                Ok(Expr::Unary(UnaryOp::Not, Box::new(expr), span))
+                let mut elements = Vec::new();
+
+                while !matches!(self.current(), Token::RBracket) {
+                    elements.push(self.parse_expression(0)?);
+                    if matches!(self.current(), Token::Comma) {
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+
+                self.expect(Token::RBracket)?;
+                Ok(Expr::ArrayLiteral(elements, span))
             }
             t => Err(format!(
                 "Error[E102]: Expected expression, found '{}' at line {}, column {}",
@@ -1083,6 +2116,368 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Check whether the `{` at the current token opens a dictionary literal
+    /// rather than a block: `{}`, or an expression followed by `:` (which no
+    /// statement starts with)
+    fn at_dict_literal(&mut self) -> bool {
+        if matches!(self.peek(1), Token::RBrace) {
+            return true;
+        }
+        let saved = (self.position, self.current_line, self.current_column);
+        self.advance();
+        let is_dict = self.parse_expression(0).is_ok() && matches!(self.current(), Token::Colon);
+        (self.position, self.current_line, self.current_column) = saved;
+        is_dict
+    }
+
+    /// Parse a dictionary literal: `{ key1: value1, key2: value2 }`
+    fn parse_dict_literal(&mut self) -> Result<Expr, String> {
+        let span = self.span();
+        self.expect(Token::LBrace)?;
+        let mut entries = Vec::new();
+
+        while !matches!(self.current(), Token::RBrace) {
+            let key = self.parse_expression(0)?;
+            self.expect(Token::Colon)?;
+            let value = self.parse_expression(0)?;
+            entries.push((key, value));
+            if matches!(self.current(), Token::Comma) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        self.expect(Token::RBrace)?;
+        Ok(Expr::DictLiteral(entries, span))
+    }
+
+    /// Parse a parenthesized, comma-separated argument list: `(arg1, arg2)`
+    fn parse_call_arguments(&mut self) -> Result<Vec<Expr>, String> {
+        self.expect(Token::LParen)?;
+        let mut args = Vec::new();
+
+        while !matches!(self.current(), Token::RParen) {
+            args.push(self.parse_expression(0)?);
+            if matches!(self.current(), Token::Comma) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        self.expect(Token::RParen)?;
+        Ok(args)
+    }
+
+    /// Parse match expression: `match scrutinee { pattern => arm, ... }`
+    ///
+    /// Each arm is either a single expression followed by a comma (optional on
+    /// the last arm) or a braced block of statements.
+    fn parse_match_expression(&mut self) -> Result<Expr, String> {
+        let span = self.span();
+        self.expect(Token::Match)?;
+
+        let scrutinee = self.parse_expression(0)?;
+        self.expect(Token::LBrace)?;
+
+        let mut arms = Vec::new();
+        while !matches!(self.current(), Token::RBrace) {
+            let arm_span = self.span();
+            let pattern = self.parse_pattern()?;
+            self.expect(Token::FatArrow)?;
+
+            if matches!(self.current(), Token::LBrace) {
+                let (body, value) = self.parse_block()?;
+
+                if matches!(self.current(), Token::Comma) {
+                    self.advance();
+                }
+
+                arms.push(MatchArm {
+                    pattern,
+                    body,
+                    value,
+                    span: arm_span,
+                });
+            } else {
+                let value = self.parse_expression(0)?;
+                if !matches!(self.current(), Token::RBrace) {
+                    self.expect(Token::Comma)?;
+                }
+
+                arms.push(MatchArm {
+                    pattern,
+                    body: Vec::new(),
+                    value: Some(value),
+                    span: arm_span,
+                });
+            }
+        }
+
+        self.expect(Token::RBrace)?;
+
+        Ok(Expr::Match {
+            scrutinee: Box::new(scrutinee),
+            arms,
+            span,
+        })
+    }
+
+    /// Parse a closure: `|x, y: i32| body`, or `|| body` without parameters
+    fn parse_lambda(&mut self) -> Result<Expr, String> {
+        let span = self.span();
+        let mut params = Vec::new();
+        if matches!(self.advance(), Token::Pipe) {
+            while !matches!(self.current(), Token::Pipe) {
+                let name = match self.advance() {
+                    Token::Ident(name) => name,
+                    t => {
+                        let base_msg = format!(
+                            "Expected closure parameter name, found {} at line {}, column {}",
+                            t.name(),
+                            self.current_line,
+                            self.current_column
+                        );
+                        return Err(format_error_with_code(
+                            ErrorCode::E111,
+                            &base_msg,
+                            self.source,
+                            self.current_line,
+                            self.current_column,
+                            "Closure parameters are names with optional types (e.g., |x, y: i32| x + y)",
+                        ));
+                    }
+                };
+                let ty = if matches!(self.current(), Token::Colon) {
+                    self.advance();
+                    Some(self.parse_type("parameter")?)
+                } else {
+                    None
+                };
+                params.push((name, ty));
+
+                if matches!(self.current(), Token::Comma) {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+            self.expect(Token::Pipe)?;
+        }
+
+        // `|| {}` is a closure that does nothing, not one returning an empty dictionary
+        let body =
+            if matches!(self.current(), Token::LBrace) && matches!(self.peek(1), Token::RBrace) {
+                self.parse_block_expression()?
+            } else {
+                self.parse_expression(0)?
+            };
+        Ok(Expr::Lambda {
+            params,
+            body: Box::new(body),
+            span,
+        })
+    }
+
+    /// Parse an if expression: `if cond { ... } else if cond { ... } else { ... }`
+    fn parse_if_expression(&mut self) -> Result<Expr, String> {
+        let span = self.span();
+        self.expect(Token::If)?;
+        if matches!(self.current(), Token::Let) {
+            return self.parse_if_let(span);
+        }
+
+        let cond = self.parse_expression(0)?;
+        let then_branch = self.parse_block_expression()?;
+
+        let else_branch = if matches!(self.current(), Token::Else) {
+            self.advance();
+            if matches!(self.current(), Token::If) {
+                Some(Box::new(self.parse_if_expression()?))
+            } else {
+                Some(Box::new(self.parse_block_expression()?))
+            }
+        } else {
+            None
+        };
+
+        Ok(Expr::If {
+            cond: Box::new(cond),
+            then_branch: Box::new(then_branch),
+            else_branch,
+            span,
+        })
+    }
+
+    /// Parse the rest of `if let pattern = value { ... } else { ... }` as a match
+    /// whose wildcard arm holds the else branch (empty without `else`)
+    fn parse_if_let(&mut self, span: Span) -> Result<Expr, String> {
+        self.expect(Token::Let)?;
+        let pattern = self.parse_pattern()?;
+        self.expect(Token::Equal)?;
+        let scrutinee = self.parse_expression(0)?;
+        let (body, value) = self.parse_block()?;
+        let mut arms = vec![MatchArm {
+            pattern,
+            body,
+            value,
+            span,
+        }];
+
+        let else_span = self.span();
+        let (body, value) = if matches!(self.current(), Token::Else) {
+            self.advance();
+            if matches!(self.current(), Token::If) {
+                (Vec::new(), Some(self.parse_if_expression()?))
+            } else {
+                self.parse_block()?
+            }
+        } else {
+            (Vec::new(), None)
+        };
+        arms.push(MatchArm {
+            pattern: Pattern::Wildcard(else_span),
+            body,
+            value,
+            span: else_span,
+        });
+
+        Ok(Expr::Match {
+            scrutinee: Box::new(scrutinee),
+            arms,
+            span,
+        })
+    }
+
+    fn parse_block_expression(&mut self) -> Result<Expr, String> {
+        let span = self.span();
+        let (stmts, value) = self.parse_block()?;
+        Ok(Expr::Block {
+            stmts,
+            value: value.map(Box::new),
+            span,
+        })
+    }
+
+    /// Parse `{ stmts; value }` into its statements and final value expression.
+    ///
+    /// The value is a last expression not followed by a semicolon. Block-like
+    /// expressions (`if`, `match`, `{ }`) need no semicolon when they are not last.
+    fn parse_block(&mut self) -> Result<(Vec<Stmt>, Option<Expr>), String> {
+        self.expect(Token::LBrace)?;
+
+        let mut stmts = Vec::new();
+        let mut value = None;
+        while !matches!(self.current(), Token::RBrace) {
+            if let Some(expr) = value.take() {
+                stmts.push(Stmt::Expr(expr));
+            }
+
+            match self.current() {
+                Token::If | Token::Match | Token::LBrace => {
+                    let expr = self.parse_primary()?;
+                    if matches!(self.current(), Token::Semicolon) {
+                        self.advance();
+                        stmts.push(Stmt::Expr(expr));
+                    } else {
+                        value = Some(expr);
+                    }
+                }
+                Token::Let
+                | Token::At
+                | Token::While
+                | Token::For
+                | Token::Loop
+                | Token::Label(_)
+                | Token::Break
+                | Token::Continue
+                | Token::Return => stmts.push(self.parse_statement()?),
+                _ => {
+                    let span = self.span();
+                    let expr = self.parse_expression(0)?;
+                    if matches!(self.current(), Token::RBrace) {
+                        value = Some(expr);
+                    } else {
+                        stmts.push(self.finish_expression_statement(expr, span)?);
+                    }
+                }
+            }
+        }
+        self.expect(Token::RBrace)?;
+
+        Ok((stmts, value))
+    }
+
+    /// Parse a match arm pattern: `_`, a literal, `Enum.Variant`, `Some(name)`,
+    /// `None`, `Ok(name)` or `Err(name)`
+    fn parse_pattern(&mut self) -> Result<Pattern, String> {
+        let span = self.span();
+
+        match self.advance() {
+            Token::Ident(name) if name == "_" => Ok(Pattern::Wildcard(span)),
+            Token::Ident(name)
+                if matches!(name.as_str(), "Some" | "Ok" | "Err")
+                    && matches!(self.current(), Token::LParen) =>
+            {
+                self.advance();
+                let binding = match self.advance() {
+                    Token::Ident(binding) => binding,
+                    t => return Err(self.pattern_error(&t)),
+                };
+                self.expect(Token::RParen)?;
+                Ok(match name.as_str() {
+                    "Some" => Pattern::Some { binding, span },
+                    "Ok" => Pattern::Ok { binding, span },
+                    _ => Pattern::Err { binding, span },
+                })
+            }
+            Token::Ident(name) if name == "None" && !matches!(self.current(), Token::Dot) => {
+                Ok(Pattern::None(span))
+            }
+            Token::Ident(enum_name) => {
+                let enum_name = self.qualified_name(enum_name);
+                self.expect(Token::Dot)?;
+                match self.advance() {
+                    Token::Ident(variant) => Ok(Pattern::EnumVariant {
+                        enum_name,
+                        variant,
+                        span,
+                    }),
+                    t => Err(self.pattern_error(&t)),
+                }
+            }
+            Token::Int(n) => Ok(Pattern::Literal(Literal::Int(n), span)),
+            Token::Float(n) => Ok(Pattern::Literal(Literal::Float(n), span)),
+            Token::Minus => match self.advance() {
+                Token::Int(n) => Ok(Pattern::Literal(Literal::Int(-n), span)),
+                Token::Float(n) => Ok(Pattern::Literal(Literal::Float(-n), span)),
+                t => Err(self.pattern_error(&t)),
+            },
+            Token::StringLit(s) => Ok(Pattern::Literal(Literal::Str(s), span)),
+            Token::True => Ok(Pattern::Literal(Literal::Bool(true), span)),
+            Token::False => Ok(Pattern::Literal(Literal::Bool(false), span)),
+            t => Err(self.pattern_error(&t)),
+        }
+    }
+
+    fn pattern_error(&self, found: &Token) -> String {
+        let base_msg = format!(
+            "Expected match pattern, found {} at line {}, column {}",
+            found.name(),
+            self.current_line,
+            self.current_column
+        );
+        format_error_with_code(
+            ErrorCode::E102,
+            &base_msg,
+            self.source,
+            self.current_line,
+            self.current_column,
+            "Patterns can be '_', a literal, an enum variant (e.g., Dir.Left), Some(name), None, Ok(name) or Err(name)",
+        )
+    }
+
     /// Parse struct literal: `TypeName { field1: expr1, field2: expr2 }`
     /// MVP: Does NOT support nested struct literals (e.g., Rect2 { position: Vector2 { x: 0.0, y: 0.0 } })
     /// Use variable references instead: let pos = ...; Rect2 { position: pos, ... }
@@ -1151,19 +2546,46 @@ impl<'a> Parser<'a> {
             Token::And => 2,
             Token::EqualEqual | Token::NotEqual => 3,
             Token::Less | Token::LessEqual | Token::Greater | Token::GreaterEqual => 4,
-            Token::Plus | Token::Minus => 5,
-            Token::Star | Token::Slash => 6,
-            Token::Dot => 7, // Highest precedence for field access
+            Token::Pipe => 5,
+            Token::Caret => 6,
+            Token::Amp => 7,
+            Token::Shl | Token::Shr => 8,
+            Token::Plus | Token::Minus => 9,
+            Token::Star | Token::Slash | Token::Percent => 10,
+            Token::Dot => 11, // Highest precedence for field access
             _ => 0,
         }
     }
 
+    /// The compound assignment written by `token`, if it is one (`+=`, `<<=`, ...)
+    fn compound_op(token: &Token) -> Option<CompoundOp> {
+        match token {
+            Token::PlusEqual => Some(CompoundOp::AddAssign),
+            Token::MinusEqual => Some(CompoundOp::SubAssign),
+            Token::StarEqual => Some(CompoundOp::MulAssign),
+            Token::SlashEqual => Some(CompoundOp::DivAssign),
+            Token::PercentEqual => Some(CompoundOp::RemAssign),
+            Token::AmpEqual => Some(CompoundOp::BitAndAssign),
+            Token::PipeEqual => Some(CompoundOp::BitOrAssign),
+            Token::CaretEqual => Some(CompoundOp::BitXorAssign),
+            Token::ShlEqual => Some(CompoundOp::ShlAssign),
+            Token::ShrEqual => Some(CompoundOp::ShrAssign),
+            _ => None,
+        }
+    }
+
     fn token_to_binary_op(&self, token: &Token) -> Result<BinaryOp, String> {
         match token {
             Token::Plus => Ok(BinaryOp::Add),
             Token::Minus => Ok(BinaryOp::Sub),
             Token::Star => Ok(BinaryOp::Mul),
             Token::Slash => Ok(BinaryOp::Div),
+            Token::Percent => Ok(BinaryOp::Rem),
+            Token::Amp => Ok(BinaryOp::BitAnd),
+            Token::Pipe => Ok(BinaryOp::BitOr),
+            Token::Caret => Ok(BinaryOp::BitXor),
+            Token::Shl => Ok(BinaryOp::Shl),
+            Token::Shr => Ok(BinaryOp::Shr),
             Token::EqualEqual => Ok(BinaryOp::Eq),
             Token::NotEqual => Ok(BinaryOp::Ne),
             Token::Less => Ok(BinaryOp::Lt),
@@ -1185,7 +2607,7 @@ impl<'a> Parser<'a> {
                     self.source,
                     self.current_line,
                     self.current_column,
-                    "Valid binary operators: +, -, *, /, ==, !=, <, <=, >, >=, and, or",
+                    "Valid binary operators: +, -, *, /, %, &, |, ^, <<, >>, ==, !=, <, <=, >, >=, &&, ||",
                 ))
             }
         }
@@ -1262,14 +2684,25 @@ pub fn parse(tokens: &[Token], source: &str) -> Result<Program, String> {
 /// - Complex programs: ~8μs
 /// - O(n) complexity where n = number of tokens
 pub fn parse_positioned(tokens: &[PositionedToken], source: &str) -> Result<Program, String> {
+    parse_file(tokens, source, FileId::MAIN)
+}
+
+/// Parse positioned tokens like [`parse_positioned`], marking every span as
+/// belonging to `file`.
+pub fn parse_file(
+    tokens: &[PositionedToken],
+    source: &str,
+    file: FileId,
+) -> Result<Program, String> {
     let mut parser = Parser::new(tokens.to_vec(), source);
+    parser.file = file;
     parser.parse_program()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lexer::tokenize;
+    use crate::lexer::{tokenize, tokenize_positioned};
 
     // Helper function to convert tokens to positioned tokens for testing
     fn to_positioned(tokens: Vec<Token>) -> Vec<PositionedToken> {
@@ -1279,205 +2712,857 @@ mod tests {
             .collect()
     }
 
-    #[test]
-    fn test_parse_empty() {
-        let source = "";
-        let tokens = vec![Token::Eof];
-        let program = parse(&tokens, source).unwrap();
-        assert_eq!(program.functions.len(), 0);
-        assert_eq!(program.global_vars.len(), 0);
-        assert_eq!(program.signals.len(), 0);
+    #[test]
+    fn test_parse_empty() {
+        let source = "";
+        let tokens = vec![Token::Eof];
+        let program = parse(&tokens, source).unwrap();
+        assert_eq!(program.functions.len(), 0);
+        assert_eq!(program.global_vars.len(), 0);
+        assert_eq!(program.signals.len(), 0);
+    }
+
+    #[test]
+    fn test_parse_signal_no_params() {
+        let input = "signal player_died();";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        assert_eq!(program.signals.len(), 1);
+        let signal = &program.signals[0];
+        assert_eq!(signal.name, "player_died");
+        assert_eq!(signal.parameters.len(), 0);
+    }
+
+    #[test]
+    fn test_parse_signal_one_param() {
+        let input = "signal health_changed(new_health: i32);";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        assert_eq!(program.signals.len(), 1);
+        let signal = &program.signals[0];
+        assert_eq!(signal.name, "health_changed");
+        assert_eq!(signal.parameters.len(), 1);
+        assert_eq!(signal.parameters[0].0, "new_health");
+        assert_eq!(signal.parameters[0].1, "i32");
+    }
+
+    #[test]
+    fn test_parse_signal_multiple_params() {
+        let input = "signal score_changed(old: i32, new: i32, reason: String);";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        assert_eq!(program.signals.len(), 1);
+        let signal = &program.signals[0];
+        assert_eq!(signal.name, "score_changed");
+        assert_eq!(signal.parameters.len(), 3);
+        assert_eq!(signal.parameters[0], ("old".to_string(), "i32".to_string()));
+        assert_eq!(signal.parameters[1], ("new".to_string(), "i32".to_string()));
+        assert_eq!(
+            signal.parameters[2],
+            ("reason".to_string(), "String".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_signal_missing_semicolon() {
+        let input = "signal player_died()";
+        let tokens = tokenize(input).unwrap();
+        let result = parse(&tokens, input);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Expected ;"));
+    }
+
+    #[test]
+    fn test_parse_signal_missing_parens() {
+        let _input = "signal player_died;";
+        let tokens = vec![
+            Token::Signal,
+            Token::Ident("player_died".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+        let result = parse(&tokens, "signal player_died;");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Expected ("));
+    }
+
+    #[test]
+    fn test_parse_signal_invalid_param_syntax() {
+        let input = "signal test(x y);";
+        let tokens = tokenize(input).unwrap();
+        let result = parse(&tokens, input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_simple_function() {
+        let input = "fn test() {}";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        assert_eq!(program.functions.len(), 1);
+        assert_eq!(program.functions[0].name, "test");
+        assert_eq!(program.functions[0].params.len(), 0);
+        assert_eq!(program.functions[0].body.len(), 0);
+    }
+
+    #[test]
+    fn test_parse_function_with_params() {
+        let input = "fn add(x: i32, y: i32) {}";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        assert_eq!(program.functions.len(), 1);
+        let func = &program.functions[0];
+        assert_eq!(func.name, "add");
+        assert_eq!(func.params.len(), 2);
+        assert_eq!(func.params[0].name, "x");
+        assert_eq!(func.params[0].ty, "i32");
+        assert_eq!(func.params[1].name, "y");
+        assert_eq!(func.params[1].ty, "i32");
+    }
+
+    #[test]
+    fn test_parse_let_statement() {
+        let input = "fn test() { let x = 5; }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        assert_eq!(program.functions[0].body.len(), 1);
+        match &program.functions[0].body[0] {
+            Stmt::Let {
+                name, mutable, ty, ..
+            } => {
+                assert_eq!(name, "x");
+                assert!(!(*mutable));
+                assert_eq!(*ty, None);
+            }
+            _ => panic!("Expected Let statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_let_mut_with_type() {
+        let input = "fn test() { let mut x: i32 = 5; }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::Let {
+                name, mutable, ty, ..
+            } => {
+                assert_eq!(name, "x");
+                assert!(*mutable);
+                assert_eq!(ty.as_ref().unwrap(), "i32");
+            }
+            _ => panic!("Expected Let statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_if_statement() {
+        let input = "fn test() { if x > 5 { let y = 10; } }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                assert_eq!(then_branch.len(), 1);
+                assert_eq!(else_branch.len(), 0);
+            }
+            _ => panic!("Expected If statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_if_else_statement() {
+        let input = "fn test() { if x { let a = 1; } else { let b = 2; } }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                assert_eq!(then_branch.len(), 1);
+                assert_eq!(else_branch.len(), 1);
+            }
+            _ => panic!("Expected If statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_while_statement() {
+        let input = "fn test() { while x < 10 { x = x + 1; } }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::While { body, .. } => {
+                assert_eq!(body.len(), 1);
+            }
+            _ => panic!("Expected While statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_for_in_array() {
+        let input = "fn test() { for x in items { print(x); } }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::For {
+                var,
+                iterable,
+                body,
+                ..
+            } => {
+                assert_eq!(var, "x");
+                assert!(matches!(iterable, Expr::Variable(name, _) if name == "items"));
+                assert_eq!(body.len(), 1);
+            }
+            _ => panic!("Expected For statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_for_in_range() {
+        let input = "fn test() { for i in 0..n { } }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::For { iterable, .. } => {
+                assert!(matches!(iterable, Expr::Range(_, _, _)));
+            }
+            _ => panic!("Expected For statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_array_literal_and_index() {
+        let input = "fn test() { let xs: Array<i32> = [1, 2, 3]; let y = xs[1]; }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::Let { ty, value, .. } => {
+                assert_eq!(ty.as_deref(), Some("Array<i32>"));
+                assert!(matches!(value, Expr::ArrayLiteral(elements, _) if elements.len() == 3));
+            }
+            _ => panic!("Expected Let statement"),
+        }
+
+        match &program.functions[0].body[1] {
+            Stmt::Let { value, .. } => {
+                assert!(matches!(value, Expr::Index(_, _, _)));
+            }
+            _ => panic!("Expected Let statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_nested_array_type() {
+        let input = "fn grid(cells: Array<Array<f32>>) -> Array<i32> { return []; }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        let func = &program.functions[0];
+        assert_eq!(func.params[0].ty, "Array<Array<f32>>");
+        assert_eq!(func.return_type.as_deref(), Some("Array<i32>"));
+    }
+
+    #[test]
+    fn test_parse_labeled_loop_and_loop_control() {
+        let input = "fn test() { 'outer: loop { for i in 0..3 { continue; break 'outer; } } }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::Loop { label, body, .. } => {
+                assert_eq!(label.as_deref(), Some("outer"));
+                match &body[0] {
+                    Stmt::For { label, body, .. } => {
+                        assert_eq!(*label, None);
+                        assert!(matches!(&body[0], Stmt::Continue { label: None, .. }));
+                        assert!(
+                            matches!(&body[1], Stmt::Break { label: Some(l), .. } if l == "outer")
+                        );
+                    }
+                    _ => panic!("Expected For statement"),
+                }
+            }
+            _ => panic!("Expected Loop statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_label_without_loop() {
+        let input = "fn test() { 'outer: if true { } }";
+        let tokens = tokenize(input).unwrap();
+        let err = parse(&tokens, input).unwrap_err();
+        assert!(err.contains("Expected loop after label"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_for_missing_in() {
+        let input = "fn test() { for x items { } }";
+        let tokens = tokenize(input).unwrap();
+        let result = parse(&tokens, input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_enum_declaration() {
+        let input = "enum Dir { Left, Right, Up, Down, }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        assert_eq!(program.enums.len(), 1);
+        assert_eq!(program.enums[0].name, "Dir");
+        assert_eq!(
+            program.enums[0].variants,
+            vec!["Left", "Right", "Up", "Down"]
+        );
+    }
+
+    #[test]
+    fn test_parse_match_statement() {
+        let input = r#"fn test() { match d { Dir.Left => print("l"), _ => { let x = 1; } } }"#;
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::Expr(Expr::Match { arms, .. }) => {
+                assert_eq!(arms.len(), 2);
+                assert!(matches!(
+                    &arms[0].pattern,
+                    Pattern::EnumVariant { enum_name, variant, .. }
+                        if enum_name == "Dir" && variant == "Left"
+                ));
+                assert!(arms[0].value.is_some());
+                assert!(matches!(arms[1].pattern, Pattern::Wildcard(_)));
+                assert!(arms[1].value.is_none());
+                assert_eq!(arms[1].body.len(), 1);
+            }
+            _ => panic!("Expected match statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_match_expression_literal_patterns() {
+        let input = "fn test() { let s = match n { -1 => 0, 0 => 1, _ => 2 }; }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::Let {
+                value: Expr::Match { arms, .. },
+                ..
+            } => {
+                assert_eq!(arms.len(), 3);
+                assert!(matches!(
+                    arms[0].pattern,
+                    Pattern::Literal(Literal::Int(-1), _)
+                ));
+            }
+            _ => panic!("Expected let with match expression"),
+        }
+    }
+
+    #[test]
+    fn test_parse_if_expression() {
+        let input = "fn test() { let s = if a { 1 } else if b { 2 } else { let c = 3; c }; }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::Let {
+                value:
+                    Expr::If {
+                        then_branch,
+                        else_branch: Some(else_branch),
+                        ..
+                    },
+                ..
+            } => {
+                assert!(
+                    matches!(&**then_branch, Expr::Block { stmts, value: Some(_), .. } if stmts.is_empty())
+                );
+                match &**else_branch {
+                    Expr::If {
+                        else_branch: Some(last),
+                        ..
+                    } => {
+                        assert!(
+                            matches!(&**last, Expr::Block { stmts, value: Some(_), .. } if stmts.len() == 1)
+                        );
+                    }
+                    _ => panic!("Expected else-if"),
+                }
+            }
+            _ => panic!("Expected let with if expression"),
+        }
+    }
+
+    #[test]
+    fn test_parse_block_expression() {
+        let input = "fn test() { let d = { let dx = b - a; dx = dx * 2; if dx > 0 { print(\"pos\"); } dx }; }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::Let {
+                value: Expr::Block { stmts, value, .. },
+                ..
+            } => {
+                assert_eq!(stmts.len(), 3);
+                assert!(matches!(&stmts[1], Stmt::Assign { .. }));
+                assert!(matches!(
+                    &stmts[2],
+                    Stmt::Expr(Expr::If {
+                        else_branch: None,
+                        ..
+                    })
+                ));
+                assert!(matches!(value.as_deref(), Some(Expr::Variable(name, _)) if name == "dx"));
+            }
+            _ => panic!("Expected let with block expression"),
+        }
+    }
+
+    #[test]
+    fn test_parse_cast_precedence() {
+        let input = "fn test() { let i = -pos.x as i32 * 2; }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        match &program.functions[0].body[0] {
+            Stmt::Let {
+                value: Expr::Binary(left, BinaryOp::Mul, _, _),
+                ..
+            } => match &**left {
+                Expr::Cast(operand, ty, _) => {
+                    assert_eq!(ty, "i32");
+                    assert!(matches!(&**operand, Expr::Unary(UnaryOp::Neg, _, _)));
+                }
+                _ => panic!("Expected cast as left operand"),
+            },
+            _ => panic!("Expected let with multiplication"),
+        }
+
+        let input = "fn test() { let i = x as 5; }";
+        let tokens = tokenize(input).unwrap();
+        let err = parse(&tokens, input).unwrap_err();
+        assert!(err.contains("E110"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_lambda_and_function_type() {
+        let input = "fn apply(f: fn(i32) -> bool, x: i32) -> bool { let g = |a, b: i32| a + b; let h = || 1; return f(x); }";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        let func = &program.functions[0];
+        assert_eq!(func.params[0].ty, "fn(i32) -> bool");
+        match &func.body[0] {
+            Stmt::Let {
+                value: Expr::Lambda { params, body, .. },
+                ..
+            } => {
+                assert_eq!(params[0], ("a".to_string(), None));
+                assert_eq!(params[1], ("b".to_string(), Some("i32".to_string())));
+                assert!(matches!(&**body, Expr::Binary(_, BinaryOp::Add, _, _)));
+            }
+            _ => panic!("Expected let with lambda"),
+        }
+        assert!(matches!(
+            &func.body[1],
+            Stmt::Let { value: Expr::Lambda { params, .. }, .. } if params.is_empty()
+        ));
+
+        let input = "let handlers: Array<fn(i32)> = [];";
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+        assert_eq!(program.global_vars[0].ty.as_deref(), Some("Array<fn(i32)>"));
+
+        let input = "fn test() { let f = |1| 2; }";
+        let tokens = tokenize(input).unwrap();
+        let err = parse(&tokens, input).unwrap_err();
+        assert!(err.contains("E111"), "{}", err);
     }
 
     #[test]
-    fn test_parse_signal_no_params() {
-        let input = "signal player_died();";
+    fn test_parse_if_let() {
+        let input = "fn test() { if let Some(n) = try_get_node(\"A\") { print(\"a\"); } else if let None = x { } else { } }";
         let tokens = tokenize(input).unwrap();
         let program = parse(&tokens, input).unwrap();
 
-        assert_eq!(program.signals.len(), 1);
-        let signal = &program.signals[0];
-        assert_eq!(signal.name, "player_died");
-        assert_eq!(signal.parameters.len(), 0);
-    }
+        match &program.functions[0].body[0] {
+            Stmt::Expr(Expr::Match { arms, .. }) => {
+                assert_eq!(arms.len(), 2);
+                assert!(
+                    matches!(&arms[0].pattern, Pattern::Some { binding, .. } if binding == "n")
+                );
+                assert_eq!(arms[0].body.len(), 1);
+                assert!(matches!(arms[1].pattern, Pattern::Wildcard(_)));
+                match &arms[1].value {
+                    Some(Expr::Match { arms, .. }) => {
+                        assert!(matches!(arms[0].pattern, Pattern::None(_)));
+                        assert!(matches!(arms[1].pattern, Pattern::Wildcard(_)));
+                    }
+                    _ => panic!("Expected else-if-let match"),
+                }
+            }
+            _ => panic!("Expected if let as match"),
+        }
 
-    #[test]
-    fn test_parse_signal_one_param() {
-        let input = "signal health_changed(new_health: i32);";
+        // Without else, the wildcard arm is empty
+        let input =
+            "fn test() { let n = if let Some(v) = x { v } else { 0 }; if let Some(_) = x {} }";
         let tokens = tokenize(input).unwrap();
         let program = parse(&tokens, input).unwrap();
+        assert!(matches!(
+            &program.functions[0].body[0],
+            Stmt::Let {
+                value: Expr::Match { .. },
+                ..
+            }
+        ));
+        match &program.functions[0].body[1] {
+            Stmt::Expr(Expr::Match { arms, .. }) => {
+                assert!(arms[1].body.is_empty() && arms[1].value.is_none());
+            }
+            _ => panic!("Expected if let as match"),
+        }
 
-        assert_eq!(program.signals.len(), 1);
-        let signal = &program.signals[0];
-        assert_eq!(signal.name, "health_changed");
-        assert_eq!(signal.parameters.len(), 1);
-        assert_eq!(signal.parameters[0].0, "new_health");
-        assert_eq!(signal.parameters[0].1, "i32");
+        let input = "fn test() { if let Some(1) = x {} }";
+        let tokens = tokenize(input).unwrap();
+        let err = parse(&tokens, input).unwrap_err();
+        assert!(err.contains("E102"), "{}", err);
     }
 
     #[test]
-    fn test_parse_signal_multiple_params() {
-        let input = "signal score_changed(old: i32, new: i32, reason: String);";
+    fn test_parse_result_and_try() {
+        let input = "fn load(s: String) -> Result<Array<i32>, String> { let v = parse(s)?.len(); match check(v) { Ok(n) => n, Err(_) => 0 } }";
         let tokens = tokenize(input).unwrap();
         let program = parse(&tokens, input).unwrap();
 
-        assert_eq!(program.signals.len(), 1);
-        let signal = &program.signals[0];
-        assert_eq!(signal.name, "score_changed");
-        assert_eq!(signal.parameters.len(), 3);
-        assert_eq!(signal.parameters[0], ("old".to_string(), "i32".to_string()));
-        assert_eq!(signal.parameters[1], ("new".to_string(), "i32".to_string()));
+        let func = &program.functions[0];
         assert_eq!(
-            signal.parameters[2],
-            ("reason".to_string(), "String".to_string())
+            func.return_type.as_deref(),
+            Some("Result<Array<i32>, String>")
         );
+        match &func.body[0] {
+            Stmt::Let {
+                value: Expr::MethodCall { receiver, .. },
+                ..
+            } => assert!(
+                matches!(&**receiver, Expr::Try(inner, _) if matches!(&**inner, Expr::Call(name, _, _) if name == "parse"))
+            ),
+            _ => panic!("Expected let with method call on ?"),
+        }
+        match &func.body[1] {
+            Stmt::Expr(Expr::Match { arms, .. }) => {
+                assert!(matches!(&arms[0].pattern, Pattern::Ok { binding, .. } if binding == "n"));
+                assert!(matches!(&arms[1].pattern, Pattern::Err { binding, .. } if binding == "_"));
+            }
+            _ => panic!("Expected match on Result"),
+        }
     }
 
     #[test]
-    fn test_parse_signal_missing_semicolon() {
-        let input = "signal player_died()";
+    fn test_parse_dict_literal_and_entry_loop() {
+        let input = r#"fn test() {
+            let prices: Dict<String, i32> = { "sword": 10, "shield": 25 };
+            let empty: Dict<i32, f32> = {};
+            let total = { let n = 1; n };
+            let f = || {};
+            for (name, price) in prices { print(name); }
+        }"#;
         let tokens = tokenize(input).unwrap();
-        let result = parse(&tokens, input);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Expected ;"));
-    }
+        let program = parse(&tokens, input).unwrap();
 
-    #[test]
-    fn test_parse_signal_missing_parens() {
-        let _input = "signal player_died;";
-        let tokens = vec![
-            Token::Signal,
-            Token::Ident("player_died".to_string()),
-            Token::Semicolon,
-            Token::Eof,
-        ];
-        let result = parse(&tokens, "signal player_died;");
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Expected ("));
+        let body = &program.functions[0].body;
+        match &body[0] {
+            Stmt::Let { ty, value, .. } => {
+                assert_eq!(ty.as_deref(), Some("Dict<String, i32>"));
+                assert!(matches!(value, Expr::DictLiteral(entries, _) if entries.len() == 2));
+            }
+            _ => panic!("Expected let with dictionary literal"),
+        }
+        assert!(
+            matches!(&body[1], Stmt::Let { value: Expr::DictLiteral(entries, _), .. } if entries.is_empty())
+        );
+        assert!(matches!(
+            &body[2],
+            Stmt::Let {
+                value: Expr::Block { .. },
+                ..
+            }
+        ));
+        assert!(matches!(
+            &body[3],
+            Stmt::Let { value: Expr::Lambda { body, .. }, .. } if matches!(**body, Expr::Block { .. })
+        ));
+        match &body[4] {
+            Stmt::For { var, value_var, .. } => {
+                assert_eq!(var, "name");
+                assert_eq!(value_var.as_deref(), Some("price"));
+            }
+            _ => panic!("Expected for loop over entries"),
+        }
     }
 
     #[test]
-    fn test_parse_signal_invalid_param_syntax() {
-        let input = "signal test(x y);";
-        let tokens = tokenize(input).unwrap();
-        let result = parse(&tokens, input);
-        assert!(result.is_err());
-    }
+    fn test_parse_use_declarations() {
+        let input = r#"use "res://lib/combat.ferris";
+        use "../util/math.ferris" as m;
 
-    #[test]
-    fn test_parse_simple_function() {
-        let input = "fn test() {}";
-        let tokens = tokenize(input).unwrap();
-        let program = parse(&tokens, input).unwrap();
+        fn strike(hit: combat.Hit) -> i32 {
+            let kind = match hit.element { combat.Element.Fire => 1, _ => 0 };
+            let combat.Hit { amount } = hit;
+            let fresh = combat.Hit { amount: m.PI, element: combat.Element.Ice };
+            return combat.damage(amount) + other.damage(kind);
+        }"#;
+        let tokens = tokenize_positioned(input).unwrap();
+        let program = parse_file(&tokens, input, FileId(2)).unwrap();
 
-        assert_eq!(program.functions.len(), 1);
-        assert_eq!(program.functions[0].name, "test");
-        assert_eq!(program.functions[0].params.len(), 0);
-        assert_eq!(program.functions[0].body.len(), 0);
+        assert_eq!(program.uses.len(), 2);
+        assert_eq!(program.uses[0].name, "combat");
+        assert!(!program.uses[0].alias);
+        assert_eq!(program.uses[1].name, "m");
+        assert_eq!(
+            program.uses[1].to_string(),
+            r#"use "../util/math.ferris" as m;"#
+        );
+
+        let func = &program.functions[0];
+        assert_eq!(func.span.file, FileId(2));
+        assert_eq!(func.params[0].ty, "combat.Hit");
+        let body: Vec<String> = func.body.iter().map(|stmt| stmt.to_string()).collect();
+        assert_eq!(
+            body,
+            [
+                "let kind = match hit.element { combat.Element.Fire => 1, _ => 0, };",
+                "let combat.Hit { amount } = hit;",
+                "let fresh = combat.Hit { amount: m.PI, element: combat.Element.Ice };",
+                // Names not bound by `use` are still field accesses and method calls
+                "return (combat.damage(amount) + other.damage(kind));",
+            ]
+        );
+        match &func.body[3] {
+            Stmt::Return {
+                value: Some(Expr::Binary(left, _, right, _)),
+                ..
+            } => {
+                assert!(matches!(&**left, Expr::Call(name, _, _) if name == "combat.damage"));
+                assert!(matches!(&**right, Expr::MethodCall { .. }));
+            }
+            _ => panic!("Expected return"),
+        }
     }
 
     #[test]
-    fn test_parse_function_with_params() {
-        let input = "fn add(x: i32, y: i32) {}";
-        let tokens = tokenize(input).unwrap();
-        let program = parse(&tokens, input).unwrap();
+    fn test_parse_use_declaration_errors() {
+        for (input, expected) in [
+            ("use combat;", "Error[E100]"),
+            ("use \"lib/my-utils.ferris\";", "Error[E983]"),
+            ("use \"a/x.ferris\"; use \"b/x.ferris\";", "Error[E983]"),
+            ("use \"x.ferris\" as 5;", "Error[E109]"),
+        ] {
+            let tokens = tokenize_positioned(input).unwrap();
+            let err = parse_positioned(&tokens, input).unwrap_err();
+            assert!(err.contains(expected), "{}: {}", input, err);
+        }
 
-        assert_eq!(program.functions.len(), 1);
-        let func = &program.functions[0];
-        assert_eq!(func.name, "add");
-        assert_eq!(func.params.len(), 2);
-        assert_eq!(func.params[0].name, "x");
-        assert_eq!(func.params[0].ty, "i32");
-        assert_eq!(func.params[1].name, "y");
-        assert_eq!(func.params[1].ty, "i32");
+        // `as` makes any file usable
+        let input = "use \"lib/my-utils.ferris\" as utils;";
+        let tokens = tokenize_positioned(input).unwrap();
+        assert!(parse_positioned(&tokens, input).is_ok());
     }
 
     #[test]
-    fn test_parse_let_statement() {
-        let input = "fn test() { let x = 5; }";
+    fn test_parse_tuples_and_destructuring_let() {
+        let input = r#"fn nearest() -> (Vector2, f32) {
+            let (dir, _) = nearest();
+            let mut Vector2 { x, y: height } = dir;
+            let pair: (i32, Array<(bool, String)>) = (1, [(true, "a")],);
+            let first = pair.1.0;
+            let grouped = (1 + 2) * 3;
+        }"#;
         let tokens = tokenize(input).unwrap();
         let program = parse(&tokens, input).unwrap();
 
-        assert_eq!(program.functions[0].body.len(), 1);
-        match &program.functions[0].body[0] {
+        let func = &program.functions[0];
+        assert_eq!(func.return_type.as_deref(), Some("(Vector2, f32)"));
+        match &func.body[0] {
+            Stmt::Let { name, pattern, .. } => {
+                assert!(name.is_empty());
+                assert_eq!(
+                    pattern,
+                    &Some(LetPattern::Tuple(vec!["dir".to_string(), "_".to_string()]))
+                );
+            }
+            _ => panic!("Expected destructuring let"),
+        }
+        match &func.body[1] {
             Stmt::Let {
-                name, mutable, ty, ..
+                pattern: Some(LetPattern::Struct { type_name, fields }),
+                mutable,
+                ..
             } => {
-                assert_eq!(name, "x");
-                assert!(!(*mutable));
-                assert_eq!(*ty, None);
+                assert_eq!(type_name, "Vector2");
+                assert_eq!(
+                    fields,
+                    &[
+                        ("x".to_string(), "x".to_string()),
+                        ("y".to_string(), "height".to_string())
+                    ]
+                );
+                assert!(mutable);
             }
-            _ => panic!("Expected Let statement"),
+            _ => panic!("Expected struct pattern"),
+        }
+        match &func.body[2] {
+            Stmt::Let { ty, value, .. } => {
+                assert_eq!(ty.as_deref(), Some("(i32, Array<(bool, String)>)"));
+                assert!(matches!(value, Expr::Tuple(elements, _) if elements.len() == 2));
+            }
+            _ => panic!("Expected let with tuple"),
+        }
+        assert!(matches!(
+            &func.body[3],
+            Stmt::Let { value: Expr::FieldAccess(inner, index, _), .. }
+                if index == "0" && matches!(&**inner, Expr::FieldAccess(_, index, _) if index == "1")
+        ));
+        assert!(matches!(
+            &func.body[4],
+            Stmt::Let {
+                value: Expr::Binary(..),
+                ..
+            }
+        ));
+
+        for bad in [
+            "fn f() { let (a) = t; }",
+            "fn f() { let t: (i32) = 1; }",
+            "fn f() { let t = (1,); }",
+            "fn f() { let (a, 1) = t; }",
+        ] {
+            let tokens = tokenize(bad).unwrap();
+            assert!(parse(&tokens, bad).is_err(), "{}", bad);
         }
     }
 
     #[test]
-    fn test_parse_let_mut_with_type() {
-        let input = "fn test() { let mut x: i32 = 5; }";
+    fn test_parse_match_missing_arrow() {
+        let input = "fn test() { match n { 1 2 } }";
+        let tokens = tokenize(input).unwrap();
+        assert!(parse(&tokens, input).is_err());
+    }
+
+    #[test]
+    fn test_parse_struct_declaration() {
+        let input = "struct Enemy { hp: i32, speed: f32, path: Array<Vector2>, }";
         let tokens = tokenize(input).unwrap();
         let program = parse(&tokens, input).unwrap();
 
-        match &program.functions[0].body[0] {
-            Stmt::Let {
-                name, mutable, ty, ..
-            } => {
-                assert_eq!(name, "x");
-                assert!(*mutable);
-                assert_eq!(ty.as_ref().unwrap(), "i32");
-            }
-            _ => panic!("Expected Let statement"),
-        }
+        assert_eq!(program.structs.len(), 1);
+        let decl = &program.structs[0];
+        assert_eq!(decl.name, "Enemy");
+        let fields: Vec<(&str, &str)> = decl
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.ty.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("hp", "i32"), ("speed", "f32"), ("path", "Array<Vector2>")]
+        );
     }
 
     #[test]
-    fn test_parse_if_statement() {
-        let input = "fn test() { if x > 5 { let y = 10; } }";
+    fn test_parse_impl_block_receivers() {
+        let input = r#"impl Enemy {
+            fn new(hp: i32) -> Enemy { return Enemy { hp: hp }; }
+            fn alive(&self) -> bool { return self.hp > 0; }
+            fn hit(&mut self, dmg: i32) { self.hp = self.hp - dmg; }
+        }"#;
         let tokens = tokenize(input).unwrap();
         let program = parse(&tokens, input).unwrap();
 
-        match &program.functions[0].body[0] {
-            Stmt::If {
-                then_branch,
-                else_branch,
-                ..
-            } => {
-                assert_eq!(then_branch.len(), 1);
-                assert_eq!(else_branch.len(), 0);
-            }
-            _ => panic!("Expected If statement"),
-        }
+        assert_eq!(program.impls.len(), 1);
+        let methods = &program.impls[0].methods;
+        assert_eq!(program.impls[0].type_name, "Enemy");
+        assert_eq!(methods.len(), 3);
+        assert_eq!(methods[0].self_param, None);
+        assert_eq!(methods[0].function.params.len(), 1);
+        assert_eq!(methods[1].self_param, Some(SelfParam::Ref));
+        assert!(methods[1].function.params.is_empty());
+        assert_eq!(methods[2].self_param, Some(SelfParam::RefMut));
+        assert_eq!(methods[2].function.params[0].name, "dmg");
     }
 
     #[test]
-    fn test_parse_if_else_statement() {
-        let input = "fn test() { if x { let a = 1; } else { let b = 2; } }";
+    fn test_parse_impl_receiver_must_be_self() {
+        let input = "impl Enemy { fn hit(&mut other) { } }";
+        let tokens = tokenize(input).unwrap();
+        let result = parse(&tokens, input);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Expected 'self'"));
+    }
+
+    #[test]
+    fn test_parse_method_call() {
+        let input = "fn test() { enemy.pos.hit(1, 2); }";
         let tokens = tokenize(input).unwrap();
         let program = parse(&tokens, input).unwrap();
 
         match &program.functions[0].body[0] {
-            Stmt::If {
-                then_branch,
-                else_branch,
+            Stmt::Expr(Expr::MethodCall {
+                receiver,
+                method,
+                args,
                 ..
-            } => {
-                assert_eq!(then_branch.len(), 1);
-                assert_eq!(else_branch.len(), 1);
+            }) => {
+                assert!(matches!(&**receiver, Expr::FieldAccess(_, field, _) if field == "pos"));
+                assert_eq!(method, "hit");
+                assert_eq!(args.len(), 2);
             }
-            _ => panic!("Expected If statement"),
+            _ => panic!("Expected method call statement"),
         }
     }
 
     #[test]
-    fn test_parse_while_statement() {
-        let input = "fn test() { while x < 10 { x = x + 1; } }";
+    fn test_parse_unary_binds_looser_than_postfix() {
+        let input = "fn test() { let a = !enemy.alive(); let b = -pos.x; }";
         let tokens = tokenize(input).unwrap();
         let program = parse(&tokens, input).unwrap();
 
         match &program.functions[0].body[0] {
-            Stmt::While { body, .. } => {
-                assert_eq!(body.len(), 1);
-            }
-            _ => panic!("Expected While statement"),
+            Stmt::Let {
+                value: Expr::Unary(UnaryOp::Not, operand, _),
+                ..
+            } => assert!(matches!(&**operand, Expr::MethodCall { .. })),
+            other => panic!("Expected negated method call, got {:?}", other),
+        }
+        match &program.functions[0].body[1] {
+            Stmt::Let {
+                value: Expr::Unary(UnaryOp::Neg, operand, _),
+                ..
+            } => assert!(matches!(&**operand, Expr::FieldAccess(..))),
+            other => panic!("Expected negated field access, got {:?}", other),
         }
     }
 
@@ -1601,6 +3686,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_every_compound_assignment() {
+        for (op, expected) in [
+            ("+=", BinaryOp::Add),
+            ("-=", BinaryOp::Sub),
+            ("*=", BinaryOp::Mul),
+            ("/=", BinaryOp::Div),
+            ("%=", BinaryOp::Rem),
+            ("&=", BinaryOp::BitAnd),
+            ("|=", BinaryOp::BitOr),
+            ("^=", BinaryOp::BitXor),
+            ("<<=", BinaryOp::Shl),
+            (">>=", BinaryOp::Shr),
+        ] {
+            let input = format!("fn test() {{ x {} 2; }}", op);
+            let tokens = tokenize(&input).unwrap();
+            let program = parse(&tokens, &input).unwrap();
+            match &program.functions[0].body[0] {
+                Stmt::Assign {
+                    value: Expr::Binary(_, actual, _, _),
+                    ..
+                } => assert_eq!(*actual, expected, "{}", op),
+                other => panic!("Expected desugared assignment for {}, got {:?}", op, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_arithmetic_and_bitwise_precedence() {
+        for (source, expected) in [
+            ("a + b % c", "(a + (b % c))"),
+            ("a * b % c", "((a * b) % c)"),
+            ("a | b ^ c & d", "(a | (b ^ (c & d)))"),
+            ("a & b == c", "((a & b) == c)"),
+            ("1 << n + 1", "(1 << (n + 1))"),
+            ("a >> 2 & mask", "((a >> 2) & mask)"),
+            (
+                "x & 1 != 0 && y | 2 > 0",
+                "(((x & 1) != 0) && ((y | 2) > 0))",
+            ),
+            ("~a & -b", "((~a) & (-b))"),
+        ] {
+            let input = format!("fn test() {{ let r = {}; }}", source);
+            let tokens = tokenize(&input).unwrap();
+            let program = parse(&tokens, &input).unwrap();
+            match &program.functions[0].body[0] {
+                Stmt::Let { value, .. } => assert_eq!(value.to_string(), expected),
+                other => panic!("Expected let statement, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_string_interpolation() {
+        let input = r#"fn test() { let s = "HP: {hp}/{max_hp * 2}!"; let t = "{name}"; }"#;
+        let tokens = tokenize_positioned(input).unwrap();
+        let program = parse_positioned(&tokens, input).unwrap();
+        let body = &program.functions[0].body;
+        match (&body[0], &body[1]) {
+            (Stmt::Let { value: s, .. }, Stmt::Let { value: t, .. }) => {
+                assert_eq!(
+                    s.to_string(),
+                    r#"(((("HP: " + str(hp)) + "/") + str((max_hp * 2))) + "!")"#
+                );
+                assert_eq!(t.to_string(), "str(name)");
+            }
+            other => panic!("Expected let statements, got {:?}", other),
+        }
+
+        // An interpolation holds exactly one expression
+        let input = r#"fn test() { let s = "{a b}"; }"#;
+        let tokens = tokenize_positioned(input).unwrap();
+        let err = parse_positioned(&tokens, input).unwrap_err();
+        assert!(
+            err.contains("E101") && err.contains("string interpolation"),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn test_parse_global_var() {
         let input = "let mut dir: f32 = 1.0;";
@@ -1684,7 +3849,7 @@ fn _process(delta: f32) {
 
     #[test]
     fn test_parse_error_unexpected_token() {
-        let input = "fn test() { ~ }";
+        let input = "fn test() { $ }";
         let tokens = tokenize(input);
         assert!(tokens.is_err());
     }
@@ -1719,10 +3884,10 @@ fn _process(delta: f32) {
     #[test]
     fn test_recovery_invalid_top_level() {
         // Parser should recover from invalid top-level item
-        let input = "~ fn test() {}";
+        let input = "$ fn test() {}";
         let tokens_result = tokenize(input);
 
-        // Lexer should catch the ~ symbol first
+        // Lexer should catch the $ symbol first
         assert!(tokens_result.is_err());
     }
 
@@ -1754,20 +3919,20 @@ fn working() { let y = 10; }
     #[test]
     fn test_recovery_sync_on_fn_keyword() {
         // Parser should sync to 'fn' keyword
-        let input = "let broken = ~ fn test() {}";
+        let input = "let broken = $ fn test() {}";
         let tokens_result = tokenize(input);
 
-        // Lexer catches ~ first
+        // Lexer catches $ first
         assert!(tokens_result.is_err());
     }
 
     #[test]
     fn test_recovery_sync_on_let_keyword() {
         // Parser should sync to 'let' keyword in function body
-        let input = "fn test() { ~ let x = 5; }";
+        let input = "fn test() { $ let x = 5; }";
         let tokens_result = tokenize(input);
 
-        // Lexer catches ~ first
+        // Lexer catches $ first
         assert!(tokens_result.is_err());
     }
 
@@ -1855,7 +4020,7 @@ fn other() { return 42; }
             Token::Let,
             Token::Ident("x".to_string()),
             Token::Equal,
-            Token::Number(1.0),
+            Token::Int(1),
             Token::Semicolon,
             Token::Fn,
             Token::Ident("foo".to_string()),
@@ -1879,7 +4044,7 @@ fn other() { return 42; }
             Token::Let,
             Token::Ident("x".to_string()),
             Token::Equal,
-            Token::Number(1.0),
+            Token::Int(1),
             Token::RBrace,
             Token::Eof,
         ];
@@ -1897,7 +4062,7 @@ fn other() { return 42; }
             Token::Let,
             Token::Ident("x".to_string()),
             Token::Equal,
-            Token::Number(1.0),
+            Token::Int(1),
             Token::Semicolon,
             Token::Eof,
         ];
@@ -1919,7 +4084,7 @@ fn other() { return 42; }
             Token::Let,
             Token::Ident("x".to_string()),
             Token::Equal,
-            Token::Number(1.0),
+            Token::Int(1),
             Token::Semicolon,
             Token::Eof,
         ];
@@ -2090,7 +4255,7 @@ fn third() { let z = 15; }
         // Parser should handle sync at EOF gracefully
         let tokens = vec![
             Token::Ident("invalid".to_string()),
-            Token::Number(1.0),
+            Token::Int(1),
             // No sync points, should reach EOF
             Token::Eof,
         ];
@@ -2139,7 +4304,7 @@ fn third() { let z = 15; }
             Token::Let,
             Token::Ident("x".to_string()),
             Token::Equal,
-            Token::Number(5.0),
+            Token::Int(5),
             // Missing semicolon
             Token::RBrace,
             Token::Eof,
@@ -2721,18 +4886,23 @@ fn third() { let z = 15; }
 
     #[test]
     fn test_parser_chained_method_calls() {
-        // Test chained method/function calls
-        // ⚠️ CURRENT LIMITATION: Method chaining on call results not supported
-        // obj.method1().method2() would require field access on call expressions
+        // Test chained method calls: each call becomes the next receiver
         let input = "fn test() { obj.method1().method2(); }";
         let tokens = tokenize(input).unwrap();
-        let result = parse(&tokens, input);
+        let program = parse(&tokens, input).unwrap();
 
-        // Future enhancement: Support chaining method calls
-        assert!(
-            result.is_err(),
-            "Method chaining on call results not currently supported"
-        );
+        match &program.functions[0].body[0] {
+            Stmt::Expr(Expr::MethodCall {
+                receiver, method, ..
+            }) => {
+                assert_eq!(method, "method2");
+                assert!(matches!(
+                    &**receiver,
+                    Expr::MethodCall { method, .. } if method == "method1"
+                ));
+            }
+            _ => panic!("Expected chained method call"),
+        }
     }
 
     #[test]
@@ -2902,6 +5072,37 @@ fn _ready() {
         }
     }
 
+    #[test]
+    fn test_parse_export_range_hint_with_constants() {
+        let input = r#"
+const MAX_HP: i32 = 50 * 2;
+@export(range(0, MAX_HP, MAX_HP / 20)) let mut hp: i32 = MAX_HP;
+"#;
+        let tokens = tokenize(input).unwrap();
+        let program = parse(&tokens, input).unwrap();
+
+        assert_eq!(program.consts.len(), 1);
+        assert_eq!(program.consts[0].name, "MAX_HP");
+        assert_eq!(program.consts[0].ty, "i32");
+        match &program.global_vars[0].export.as_ref().unwrap().hint {
+            crate::ast::PropertyHint::Range { min, max, step } => {
+                assert_eq!(*min, 0.0);
+                assert_eq!(*max, 100.0);
+                assert_eq!(*step, 5.0);
+            }
+            _ => panic!("Expected PropertyHint::Range"),
+        }
+    }
+
+    #[test]
+    fn test_parse_const_requires_type() {
+        let input = "const MAX = 10;";
+        let tokens = tokenize(input).unwrap();
+        let error = parse(&tokens, input).unwrap_err();
+        assert!(error.contains("E110"), "{}", error);
+        assert!(error.contains("constant 'MAX'"), "{}", error);
+    }
+
     // Checkpoint 1.5: File property hint tests
     #[test]
     fn test_parse_export_file_hint_single_extension() {
@@ -8,6 +8,7 @@
 //!
 //! - [`Position`]: A single point in source code (line, column, byte offset)
 //! - [`Span`]: A range in source code (start and end positions)
+//! - [`FileId`]: Which file of a multi-file program a span belongs to
 //!
 //! # Examples
 //!
@@ -113,6 +114,28 @@ impl fmt::Display for Position {
     }
 }
 
+/// Identifies the source file of a span in a program built from several files.
+///
+/// The main script is [`FileId::MAIN`]; each module loaded with `use` gets the
+/// next id, which indexes the program's `files`.
+///
+/// # Examples
+///
+/// ```
+/// use ferrisscript_compiler::span::{FileId, Position, Span};
+///
+/// let span = Span::point(Position::new(3, 5, 0)).in_file(FileId(1));
+/// assert_eq!(span.file, FileId(1));
+/// assert_ne!(span.file, FileId::MAIN);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FileId(pub u32);
+
+impl FileId {
+    /// The script being compiled, as opposed to a module it uses
+    pub const MAIN: FileId = FileId(0);
+}
+
 /// A span representing a range in source code.
 ///
 /// Spans track the start and end positions of AST nodes, enabling precise
@@ -142,6 +165,8 @@ pub struct Span {
     pub start: Position,
     /// End position of the span (exclusive)
     pub end: Position,
+    /// File the span is in
+    pub file: FileId,
 }
 
 impl Span {
@@ -158,7 +183,11 @@ impl Span {
     /// );
     /// ```
     pub fn new(start: Position, end: Position) -> Self {
-        Span { start, end }
+        Span {
+            start,
+            end,
+            file: FileId::MAIN,
+        }
     }
 
     /// Create a span from a single position (zero-length span).
@@ -178,6 +207,7 @@ impl Span {
         Span {
             start: pos,
             end: pos,
+            file: FileId::MAIN,
         }
     }
 
@@ -195,6 +225,7 @@ impl Span {
         Span {
             start: Position::unknown(),
             end: Position::unknown(),
+            file: FileId::MAIN,
         }
     }
 
@@ -203,10 +234,25 @@ impl Span {
         self.start.is_unknown() && self.end.is_unknown()
     }
 
+    /// The same range in the given file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferrisscript_compiler::span::{FileId, Position, Span};
+    ///
+    /// let span = Span::point(Position::new(1, 1, 0));
+    /// assert_eq!(span.file, FileId::MAIN);
+    /// assert_eq!(span.in_file(FileId(2)).file, FileId(2));
+    /// ```
+    pub fn in_file(self, file: FileId) -> Span {
+        Span { file, ..self }
+    }
+
     /// Merge this span with another, creating a span that encompasses both.
     ///
     /// The resulting span starts at the earlier start position and ends at
-    /// the later end position.
+    /// the later end position, in this span's file.
     ///
     /// # Examples
     ///
@@ -226,6 +272,7 @@ impl Span {
         Span {
             start: min(self.start, other.start),
             end: max(self.end, other.end),
+            file: self.file,
         }
     }
 
@@ -490,6 +537,18 @@ mod tests {
         assert_eq!(format!("{}", span), "5:10 to 7:5");
     }
 
+    #[test]
+    fn test_span_in_file() {
+        let span = Span::new(Position::new(2, 1, 10), Position::new(2, 4, 13));
+        assert_eq!(span.file, FileId::MAIN);
+
+        let moved = span.in_file(FileId(3));
+        assert_eq!(moved.file, FileId(3));
+        assert_eq!((moved.start, moved.end), (span.start, span.end));
+        assert_ne!(moved, span);
+        assert_eq!(moved.merge(span).file, FileId(3));
+    }
+
     #[test]
     fn test_span_backward_compatibility() {
         #[allow(deprecated)]
@@ -26,8 +26,8 @@ fn assert_error_code(source: &str, expected_code: ErrorCode) {
 /// Test that all lexical errors (E001-E003) produce correct error codes
 #[test]
 fn test_lexical_error_codes() {
-    // E001: Invalid character (changed from @ to ~ since @ is now valid for @export)
-    assert_error_code("let x = 5 ~ 3;", ErrorCode::E001);
+    // E001: Invalid character (changed from ~ to $ since ~ is now bitwise NOT)
+    assert_error_code("let x = 5 $ 3;", ErrorCode::E001);
 
     // E002: Unterminated string
     assert_error_code("let msg = \"hello;", ErrorCode::E002);
@@ -160,12 +160,42 @@ fn test_type_checking_error_codes() {
     );
 }
 
+/// Test that module errors (E980-E983) produce correct error codes
+#[test]
+fn test_module_error_codes() {
+    use ferrisscript_compiler::compile_with_loader;
+    use ferrisscript_compiler::module::MemoryLoader;
+
+    let loader = MemoryLoader::new()
+        .with_file("res://a.ferris", "use \"b.ferris\";")
+        .with_file("res://b.ferris", "use \"a.ferris\";")
+        .with_file("res://signals.ferris", "signal died();");
+    let cases = [
+        // E980: Module not found
+        ("use \"missing.ferris\";", ErrorCode::E980),
+        // E981: Circular module use
+        ("use \"a.ferris\";", ErrorCode::E981),
+        // E982: Declaration not allowed in a module
+        ("use \"signals.ferris\";", ErrorCode::E982),
+        // E983: Invalid module name
+        ("use \"a.ferris\";\nuse \"lib/a.ferris\";", ErrorCode::E983),
+    ];
+    for (source, code) in cases {
+        let errors = compile_with_loader(source, "res://main.ferris", &loader).unwrap_err();
+        let expected = format!("Error[{}]:", code.as_str());
+        assert!(errors.contains(&expected), "{}:\n{}", source, errors);
+    }
+
+    // Without a loader every module is missing
+    assert_error_code("use \"a.ferris\";", ErrorCode::E980);
+}
+
 /// Test error code format consistency
 #[test]
 fn test_error_code_format() {
     // All error codes should match the pattern: Error[EXXX]:
     let test_cases = vec![
-        ("let x = ~;", "Error[E001]:"), // Changed from @ to ~ since @ is now valid for @export
+        ("let x = $;", "Error[E001]:"), // Changed from ~ to $ since ~ is now bitwise NOT
         ("let x = \"unterminated", "Error[E002]:"),
         ("let x = 3.14.159;", "Error[E003]:"),
         ("x = 5;", "Error[E101]:"),
@@ -100,7 +100,7 @@ mod parser_errors {
 
     #[test]
     fn test_expected_field_name_includes_position() {
-        let source = "fn test() { let x = self.123; }";
+        let source = "fn test() { let x = self.\"name\"; }";
         let tokens = lexer::tokenize(source).unwrap();
         let result = parser::parse(&tokens, source);
 
@@ -181,7 +181,7 @@ mod lexer_errors {
 
     #[test]
     fn test_unexpected_character_includes_position() {
-        let source = "let x = ~;"; // Changed from @ to ~ since @ is now valid for @export
+        let source = "let x = $;"; // Changed from ~ to $ since ~ is now bitwise NOT
         let result = lexer::tokenize(source);
 
         assert!(result.is_err());
@@ -190,34 +190,6 @@ mod lexer_errors {
         assert!(error.contains("line"));
         assert!(error.contains("column"));
     }
-
-    #[test]
-    fn test_unexpected_ampersand_includes_position() {
-        let source = "let x = 5 & 3;";
-        let result = lexer::tokenize(source);
-
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert!(error.contains("Unexpected character '&'"));
-        assert!(error.contains("&&")); // Hint mentions &&
-        assert!(error.contains("logical AND")); // Full hint text
-        assert!(error.contains("line"));
-        assert!(error.contains("column"));
-    }
-
-    #[test]
-    fn test_unexpected_pipe_includes_position() {
-        let source = "let x = true | false;";
-        let result = lexer::tokenize(source);
-
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert!(error.contains("Unexpected character '|'"));
-        assert!(error.contains("||")); // Hint mentions ||
-        assert!(error.contains("logical OR")); // Full hint text
-        assert!(error.contains("line"));
-        assert!(error.contains("column"));
-    }
 }
 
 #[cfg(test)]
@@ -285,6 +257,34 @@ mod type_checker_errors {
         assert!(error.contains("column"));
     }
 
+    #[test]
+    fn test_bitwise_and_on_bools_includes_position() {
+        let source = "fn test() { let x = true & false; }";
+        let result = compile(source);
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(error.contains("Bitwise operation & requires integer types"));
+        assert!(error.contains("&&")); // Hint mentions &&
+        assert!(error.contains("logical AND")); // Full hint text
+        assert!(error.contains("line"));
+        assert!(error.contains("column"));
+    }
+
+    #[test]
+    fn test_bitwise_or_on_bools_includes_position() {
+        let source = "fn test() { let x = true | false; }";
+        let result = compile(source);
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(error.contains("Bitwise operation | requires integer types"));
+        assert!(error.contains("||")); // Hint mentions ||
+        assert!(error.contains("logical OR")); // Full hint text
+        assert!(error.contains("line"));
+        assert!(error.contains("column"));
+    }
+
     #[test]
     fn test_undefined_function_includes_position() {
         let source = "fn test() { let x = undefined_func(); }";
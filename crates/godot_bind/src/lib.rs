@@ -1,5 +1,9 @@
-use ferrisscript_compiler::{ast, compile};
-use ferrisscript_runtime::{Env, InputEventHandle, Value, call_function, execute};
+use ferrisscript_compiler::module::ModuleLoader;
+use ferrisscript_compiler::{ast, compile_with_loader};
+use ferrisscript_runtime::{
+    DEFAULT_MAX_CALL_DEPTH, DEFAULT_RANDOM_SEED, Env, InputEventHandle, Value, call_function,
+    execute, format_value,
+};
 use godot::classes::{FileAccess, InputEvent, file_access::ModeFlags};
 use godot::prelude::*;
 use std::cell::RefCell;
@@ -28,16 +32,18 @@ fn property_usage_common() -> PropertyUsageFlags {
 
 /// Map FerrisScript type name to Godot VariantType
 ///
-/// Supports all 8 exportable types from Phase 5 Sub-Phase 2:
-/// - Primitives: i32, f32, bool, String
+/// Supports all 10 exportable types:
+/// - Primitives: i32, i64, f32, f64, bool, String
 /// - Godot structs: Vector2, Color, Rect2, Transform2D
 ///
+/// Godot's INT and FLOAT variants are 64-bit, so both widths of each map to them.
+///
 /// Returns VariantType::NIL for unknown types with a warning.
 #[allow(dead_code)]
 fn map_type_to_variant(type_name: &str) -> VariantType {
     match type_name {
-        "i32" => VariantType::INT,
-        "f32" => VariantType::FLOAT,
+        "i32" | "i64" => VariantType::INT,
+        "f32" | "f64" => VariantType::FLOAT,
         "bool" => VariantType::BOOL,
         "String" => VariantType::STRING,
         "Vector2" => VariantType::VECTOR2,
@@ -190,7 +196,7 @@ fn node_query_callback_tls(
             .map_err(|_| "Node no longer exists".to_string())?;
 
         match query_type {
-            NodeQueryType::GetNode => {
+            NodeQueryType::GetNode | NodeQueryType::TryGetNode => {
                 // Try to get the node by path
                 let target_node = node.try_get_node_as::<Node2D>(path_or_name);
                 match target_node {
@@ -200,6 +206,8 @@ fn node_query_callback_tls(
                         // In future, may need to store actual Godot node reference
                         Ok(Value::Node(NodeHandle::new(path_or_name.to_string())))
                     }
+                    // try_get_node returns None instead of failing
+                    None if query_type == NodeQueryType::TryGetNode => Ok(Value::Nil),
                     None => Err(format!("Node not found: {}", path_or_name)),
                 }
             }
@@ -218,7 +226,7 @@ fn node_query_callback_tls(
                 let has_node = node.has_node(path_or_name);
                 Ok(Value::Bool(has_node))
             }
-            NodeQueryType::FindChild => {
+            NodeQueryType::FindChild | NodeQueryType::TryFindChild => {
                 // Find child by name (recursive search)
                 // Godot's find_child takes only the name pattern
                 let child = node.find_child(path_or_name);
@@ -230,6 +238,7 @@ fn node_query_callback_tls(
                             path_or_name
                         ))))
                     }
+                    None if query_type == NodeQueryType::TryFindChild => Ok(Value::Nil),
                     None => Err(format!("Child node not found: {}", path_or_name)),
                 }
             }
@@ -239,6 +248,8 @@ fn node_query_callback_tls(
 
 /// Convert FerrisScript Value to Godot Variant
 ///
+/// `i64` and `f64` values keep their full width, since Godot's INT and FLOAT are 64-bit.
+///
 /// Handles edge cases for numeric types:
 /// - NaN floats are converted to 0.0 with a warning
 /// - Infinite floats are clamped to f32::MAX/MIN with a warning
@@ -247,6 +258,9 @@ fn node_query_callback_tls(
 fn value_to_variant(value: &Value) -> Variant {
     match value {
         Value::Int(i) => Variant::from(*i),
+        Value::Int64(i) => Variant::from(*i),
+        Value::Float64(f) if !f.is_finite() => value_to_variant(&Value::Float(*f as f32)),
+        Value::Float64(f) => Variant::from(*f),
         Value::Float(f) => {
             // Handle NaN and Infinity edge cases
             if f.is_nan() {
@@ -298,55 +312,64 @@ fn value_to_variant(value: &Value) -> Variant {
                 _ => Variant::nil(), // Invalid nested values
             }
         }
+        // Tuples cross into Godot as arrays of their elements
+        Value::Array(items) | Value::Tuple(items) => {
+            let mut array = Array::<Variant>::new();
+            for item in items {
+                array.push(&value_to_variant(item));
+            }
+            Variant::from(array)
+        }
+        Value::Dict(entries) => {
+            let mut dict = VarDictionary::new();
+            for (key, value) in entries {
+                dict.set(&value_to_variant(key), &value_to_variant(value));
+            }
+            Variant::from(dict)
+        }
+        // Enums cross into Godot as their variant name
+        Value::Enum { variant, .. } => Variant::from(variant.as_str()),
+        Value::Struct { .. } => Variant::nil(), // Script-only type with no Godot equivalent
         Value::Nil => Variant::nil(),
         Value::SelfObject => Variant::nil(), // self cannot be passed as signal parameter
         Value::InputEvent(_) => Variant::nil(), // InputEvent cannot be passed as signal parameter
         Value::Node(_) => Variant::nil(),    // Node cannot be passed as signal parameter
+        Value::Function(_) => Variant::nil(), // Script functions only run inside FerrisScript
+        Value::Result(Ok(value)) => value_to_variant(value),
+        Value::Result(Err(error)) => {
+            godot_warn!(
+                "Err value in Value→Variant conversion, defaulting to nil: {}",
+                format_value(error)
+            );
+            Variant::nil()
+        }
     }
 }
 
 /// Godot-specific print function that outputs to Godot's console
 fn godot_print_builtin(args: &[Value]) -> Result<Value, String> {
-    let output = args
-        .iter()
-        .map(|v| match v {
-            Value::Int(i) => i.to_string(),
-            Value::Float(f) => f.to_string(),
-            Value::Bool(b) => b.to_string(),
-            Value::String(s) => s.clone(),
-            Value::Vector2 { x, y } => format!("Vector2({}, {})", x, y),
-            Value::Color { r, g, b, a } => format!("Color({}, {}, {}, {})", r, g, b, a),
-            Value::Rect2 { position, size } => match (&**position, &**size) {
-                (Value::Vector2 { x: px, y: py }, Value::Vector2 { x: sx, y: sy }) => {
-                    format!("Rect2(Vector2({}, {}), Vector2({}, {}))", px, py, sx, sy)
-                }
-                _ => "Rect2(invalid, invalid)".to_string(),
-            },
-            Value::Transform2D {
-                position,
-                rotation,
-                scale,
-            } => match (&**position, &**scale) {
-                (Value::Vector2 { x: px, y: py }, Value::Vector2 { x: sx, y: sy }) => {
-                    format!(
-                        "Transform2D(Vector2({}, {}), {}, Vector2({}, {}))",
-                        px, py, rotation, sx, sy
-                    )
-                }
-                _ => "Transform2D(invalid, invalid, invalid)".to_string(),
-            },
-            Value::Nil => "nil".to_string(),
-            Value::SelfObject => "self".to_string(),
-            Value::InputEvent(_) => "InputEvent".to_string(),
-            Value::Node(handle) => format!("Node({})", handle.id()),
-        })
-        .collect::<Vec<_>>()
-        .join(" ");
+    let output = args.iter().map(format_value).collect::<Vec<_>>().join(" ");
 
     godot_print!("{}", output);
     Ok(Value::Nil)
 }
 
+/// Reads the modules a script uses through Godot's FileAccess, so `res://`
+/// paths work both in the editor and in exported projects
+struct GodotModuleLoader;
+
+impl ModuleLoader for GodotModuleLoader {
+    fn load(&self, path: &str) -> Result<String, String> {
+        FileAccess::open(path, ModeFlags::READ)
+            .map(|file| file.get_as_text().to_string())
+            .ok_or_else(|| "file not found or cannot be accessed".to_string())
+    }
+}
+
+/// Default `instruction_limit` of a [`FerrisScriptNode`], enough for heavy frame
+/// logic while still stopping a runaway loop in well under a second
+const DEFAULT_INSTRUCTION_LIMIT: i64 = 10_000_000;
+
 struct FerrisScriptExtension;
 
 #[gdextension]
@@ -362,9 +385,26 @@ pub struct FerrisScriptNode {
     #[export(file = "*.ferris")]
     script_path: GString,
 
+    /// Maximum depth of nested FerrisScript calls; deeper recursion fails with
+    /// a stack overflow error (E403) instead of exhausting memory
+    #[export]
+    max_call_depth: i32,
+
+    /// Instructions a single callback (such as one `_process`) may execute before
+    /// it is aborted with E420, so a runaway loop cannot freeze Godot; 0 disables the limit
+    #[export]
+    instruction_limit: i64,
+
+    /// Seed for the script's random number built-ins (`randi()`, `randf()`, ...),
+    /// so a scene replays the same sequence; 0 picks a new seed on every load
+    #[export]
+    random_seed: i64,
+
     // Runtime state
     env: Option<Env>,
     program: Option<ast::Program>,
+    /// Sources of the loaded script and the modules it uses, shown with runtime errors
+    files: Vec<ast::SourceFile>,
     script_loaded: bool,
 
     // Hot-reload support: Track last modified time
@@ -377,8 +417,12 @@ impl INode2D for FerrisScriptNode {
         FerrisScriptNode {
             base,
             script_path: GString::new(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH as i32,
+            instruction_limit: DEFAULT_INSTRUCTION_LIMIT,
+            random_seed: 0,
             env: None,
             program: None,
+            files: Vec::new(),
             script_loaded: false,
             last_modified: None,
         }
@@ -528,8 +572,8 @@ impl INode2D for FerrisScriptNode {
     /// 3. Inspector displays properties with correct types, hints, and default values
     /// 4. User edits trigger get() and set() calls (implemented in Bundle 7)
     ///
-    /// **Property Types Supported** (8 types from Sub-Phase 2):
-    /// - Primitives: i32, f32, bool, String
+    /// **Property Types Supported** (10 types):
+    /// - Primitives: i32, i64, f32, f64, bool, String
     /// - Godot types: Vector2, Color, Rect2, Transform2D
     ///
     /// **Property Hints Supported** (4 hints from Sub-Phase 2):
@@ -569,8 +613,8 @@ impl INode2D for FerrisScriptNode {
     /// - `Some(variant)` = We handled it, use this value from FerrisScript runtime
     /// - `None` = Not our property, fallback to Godot's default handling (e.g., Node2D.position)
     ///
-    /// **Supported Types**: All 8 exportable types from Phase 5 Sub-Phase 2:
-    /// - Primitives: i32, f32, bool, String
+    /// **Supported Types**: All 10 exportable types:
+    /// - Primitives: i32, i64, f32, f64, bool, String
     /// - Godot types: Vector2, Color, Rect2, Transform2D
     ///
     /// **Error Handling**:
@@ -656,6 +700,44 @@ impl INode2D for FerrisScriptNode {
             return true; // We handled it
         }
 
+        // ========== Special Handling: Execution Limits ==========
+        // Apply new limits to an already loaded script right away
+        let is_limit = match prop_name.as_str() {
+            "max_call_depth" => {
+                if let Ok(depth) = value.try_to::<i32>() {
+                    self.max_call_depth = depth;
+                }
+                true
+            }
+            "instruction_limit" => {
+                if let Ok(limit) = value.try_to::<i64>() {
+                    self.instruction_limit = limit;
+                }
+                true
+            }
+            _ => false,
+        };
+        if is_limit {
+            if let Some(mut env) = self.env.take() {
+                self.apply_execution_limits(&mut env);
+                self.env = Some(env);
+            }
+            return true; // We handled it
+        }
+
+        // ========== Special Handling: Random Seed ==========
+        // Restart a loaded script's random sequence from the new seed
+        if prop_name == "random_seed" {
+            if let Ok(seed) = value.try_to::<i64>() {
+                self.random_seed = seed;
+            }
+            if let Some(mut env) = self.env.take() {
+                self.apply_random_seed(&mut env);
+                self.env = Some(env);
+            }
+            return true; // We handled it
+        }
+
         // Check if we have a loaded environment with runtime storage
         if let Some(env) = &mut self.env {
             // Convert Godot Variant → FerrisScript Value
@@ -718,6 +800,26 @@ impl FerrisScriptNode {
         godot_print!("Cleared script state due to compilation/execution error");
     }
 
+    /// Copy the exported execution limits into a script environment
+    fn apply_execution_limits(&self, env: &mut Env) {
+        env.set_max_call_depth(self.max_call_depth.max(1) as usize);
+        env.set_instruction_limit(self.instruction_limit.max(0) as u64);
+    }
+
+    /// Seed a script environment's random number generator from `random_seed`,
+    /// or from the clock when it is 0
+    fn apply_random_seed(&self, env: &mut Env) {
+        let seed = if self.random_seed != 0 {
+            self.random_seed as u64
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_nanos() as u64)
+                .unwrap_or(DEFAULT_RANDOM_SEED)
+        };
+        env.set_random_seed(seed);
+    }
+
     /// Load and compile the FerrisScript file
     fn load_script(&mut self) {
         let path_gstring = self.script_path.clone();
@@ -740,7 +842,7 @@ impl FerrisScriptNode {
         let source = file.get_as_text().to_string();
 
         // Compile the script
-        let program = match compile(&source) {
+        let program = match compile_with_loader(&source, &path, &GodotModuleLoader) {
             Ok(prog) => prog,
             Err(e) => {
                 godot_error!("Failed to compile script '{}': {}", path, e);
@@ -754,13 +856,20 @@ impl FerrisScriptNode {
 
         // Override print() to use Godot's console
         env.register_builtin("print".to_string(), godot_print_builtin);
+        self.apply_execution_limits(&mut env);
+        self.apply_random_seed(&mut env);
 
         if let Err(e) = execute(&program, &mut env) {
-            godot_error!("Failed to initialize script '{}': {}", path, e);
+            godot_error!(
+                "Failed to initialize script '{}': {}",
+                path,
+                e.format_with_files(&program.files)
+            );
             self.clear_on_error();
             return;
         }
 
+        self.files = program.files.clone();
         self.program = Some(program);
         self.env = Some(env);
         self.script_loaded = true;
@@ -861,7 +970,11 @@ impl FerrisScriptNode {
         let result = match call_function(function_name, args, env) {
             Ok(value) => Some(value),
             Err(e) => {
-                godot_error!("Error calling function '{}': {}", function_name, e);
+                godot_error!(
+                    "Error calling function '{}': {}",
+                    function_name,
+                    e.format_with_files(&self.files)
+                );
                 None
             }
         };
@@ -902,7 +1015,11 @@ impl FerrisScriptNode {
         let result = match call_function(function_name, args, env) {
             Ok(value) => Some(value),
             Err(e) => {
-                godot_error!("Error calling function '{}': {}", function_name, e);
+                godot_error!(
+                    "Error calling function '{}': {}",
+                    function_name,
+                    e.format_with_files(&self.files)
+                );
                 None
             }
         };
@@ -986,13 +1103,14 @@ impl FerrisScriptNode {
 /// Convert Godot Variant to FerrisScript Value (Checkpoint 3.8 - Enhanced)
 ///
 /// Converts Inspector set operations to FerrisScript runtime values.
-/// Supports all 8 exportable types with enhanced type safety and edge case handling.
+/// Supports all 10 exportable types with enhanced type safety and edge case handling.
 ///
 /// Type checking order (CRITICAL for correctness):
 /// 1. **Boolean** - MUST be checked before numeric types to avoid bool→int misidentification
-/// 2. Integer (i32)
-/// 3. Float (f64 → f32 with NaN/Infinity handling)
-/// 4. String, Vector2, Color, Rect2, Transform2D
+/// 2. Integer (i32, or i64 when out of i32 range)
+/// 3. Float (f32 when exactly representable, otherwise f64; NaN/Infinity handled)
+/// 4. String, Vector2, Color, Rect2, Transform2D, Array and Dictionary (elements, keys and values
+///    converted the same way)
 /// 5. Nil (fallback)
 ///
 /// Edge case handling:
@@ -1011,6 +1129,9 @@ fn variant_to_value(variant: &Variant) -> Value {
     if let Ok(i) = variant.try_to::<i32>() {
         return Value::Int(i);
     }
+    if let Ok(i) = variant.try_to::<i64>() {
+        return Value::Int64(i);
+    }
 
     // Try float with NaN/Infinity handling
     if let Ok(f) = variant.try_to::<f64>() {
@@ -1031,8 +1152,11 @@ fn variant_to_value(variant: &Variant) -> Value {
             );
             return Value::Float(clamped);
         }
-        // Safe conversion for finite values
-        return Value::Float(f as f32);
+        // Keep full precision when f32 would round the value (e.g. an f64 property)
+        if f64::from(f as f32) == f {
+            return Value::Float(f as f32);
+        }
+        return Value::Float64(f);
     }
 
     // Try other Godot types
@@ -1084,14 +1208,50 @@ fn variant_to_value(variant: &Variant) -> Value {
         };
     }
 
+    if let Ok(array) = variant.try_to::<VarArray>() {
+        return Value::Array(
+            array
+                .iter_shared()
+                .map(|item| variant_to_value(&item))
+                .collect(),
+        );
+    }
+
+    if let Ok(dict) = variant.try_to::<VarDictionary>() {
+        return Value::Dict(
+            dict.iter_shared()
+                .map(|(key, value)| (variant_to_value(&key), variant_to_value(&value)))
+                .collect(),
+        );
+    }
+
     // Fallback for unrecognized types
     Value::Nil
 }
 
+/// Test hooks for the headless integration suite (godot_test/scripts/godot_bind_tests.gd)
+///
+/// Variant conversion needs an initialized engine, so it can't be covered by
+/// `cargo test`; the GDScript runner calls these methods instead.
+#[derive(GodotClass)]
+#[class(base=Node, init)]
+pub struct FerrisScriptTestNode {
+    base: Base<Node>,
+}
+
+#[godot_api]
+impl FerrisScriptTestNode {
+    /// Convert a Variant to a FerrisScript Value and back
+    #[func]
+    fn round_trip_variant(&self, value: Variant) -> Variant {
+        value_to_variant(&variant_to_value(&value))
+    }
+}
+
 // NOTE: Tests for variant conversion and PropertyInfo generation require Godot to be
 // initialized and will be validated in integration tests (godot_test/ examples).
-// The variant_to_value() and value_to_variant() functions are already used in the
-// signal emission system and known to work correctly.
+// Round trips through variant_to_value() and value_to_variant() are covered by
+// FerrisScriptTestNode in the headless godot_bind_tests.gd suite.
 
 #[cfg(test)]
 mod tests {
@@ -1125,6 +1285,16 @@ mod tests {
         assert_eq!(map_type_to_variant("f32"), VariantType::FLOAT);
     }
 
+    #[test]
+    fn test_map_type_i64() {
+        assert_eq!(map_type_to_variant("i64"), VariantType::INT);
+    }
+
+    #[test]
+    fn test_map_type_f64() {
+        assert_eq!(map_type_to_variant("f64"), VariantType::FLOAT);
+    }
+
     #[test]
     fn test_map_type_bool() {
         assert_eq!(map_type_to_variant("bool"), VariantType::BOOL);
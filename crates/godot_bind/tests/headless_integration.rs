@@ -124,6 +124,7 @@ struct TestResults {
 /// - Godot can run in headless mode
 /// - Test scene loads and executes
 /// - Basic GDScript functionality works
+/// - Variants round-trip through FerrisScript values (FerrisScriptTestNode)
 ///
 /// Uses existing test_harness infrastructure (TestConfig, GodotRunner)
 #[test]
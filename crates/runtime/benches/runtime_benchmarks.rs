@@ -253,12 +253,66 @@ fn type_operations_benchmarks(c: &mut Criterion) {
     group.finish();
 }
 
+/// Calls into an already-loaded script, the way `FerrisScriptNode` invokes
+/// `_process` every frame. Unlike the groups above, program setup is not
+/// part of the measurement.
+fn hot_path_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hot_path");
+
+    let source = r#"
+        let mut position: Vector2 = Vector2 { x: 0.0, y: 0.0 };
+        let speed: f32 = 120.0;
+
+        fn _process(delta: f32) {
+            let mut velocity: Vector2 = Vector2 { x: speed, y: speed * 0.5 };
+            if position.x > 1000.0 {
+                position.x = 0.0;
+            }
+            position.x = position.x + velocity.x * delta;
+            position.y = position.y + velocity.y * delta;
+        }
+
+        fn sum_to_n(n: i32) -> i32 {
+            let mut sum: i32 = 0;
+            for i in 0..n {
+                sum = sum + i;
+            }
+            return sum;
+        }
+
+        fn fib(n: i32) -> i32 {
+            if n < 2 {
+                return n;
+            }
+            return fib(n - 1) + fib(n - 2);
+        }
+    "#;
+    let program = compile(source).unwrap();
+    let mut env = Env::new();
+    execute(&program, &mut env).unwrap();
+
+    group.bench_function("process_frame", |b| {
+        b.iter(|| call_function("_process", &[Value::Float(black_box(0.016))], &mut env).unwrap());
+    });
+
+    group.bench_function("loop_1000_iterations", |b| {
+        b.iter(|| call_function("sum_to_n", &[Value::Int(black_box(1000))], &mut env).unwrap());
+    });
+
+    group.bench_function("fib_15", |b| {
+        b.iter(|| call_function("fib", &[Value::Int(black_box(15))], &mut env).unwrap());
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     compilation_benchmarks,
     execution_benchmarks,
     recursion_benchmarks,
     variable_operations_benchmarks,
-    type_operations_benchmarks
+    type_operations_benchmarks,
+    hot_path_benchmarks
 );
 criterion_main!(benches);
@@ -0,0 +1,1396 @@
+//! Bytecode compiler for FerrisScript functions.
+//!
+//! Lowers each `ast::Function` (and impl method) into a [`Chunk`]: a flat list of
+//! stack-machine instructions executed by the [`vm`](crate::vm). Local variables,
+//! parameters and `self` inside methods are resolved to numbered frame slots at
+//! compile time, so the VM never looks a local up by name. Constants are inlined
+//! as literal values. Only globals and host-provided variables (such as the Godot
+//! `self`) are resolved through [`Env`].
+//!
+//! Calls to functions that are already known when a chunk is compiled are bound
+//! to their index in [`Env`]'s code table; anything else (built-ins, functions
+//! defined later) is called by name. A call whose name is a variable rather than
+//! a function calls the function value that variable holds.
+//!
+//! Each closure is compiled into its own chunk, nested in the chunk that creates
+//! it. The closure's parameters come first in its frame, followed by the
+//! enclosing function's locals it refers to, which are copied into the closure
+//! value when it is created.
+//!
+//! Values stored into an `i64`, `f32` or `f64` variable, parameter or return value
+//! are converted to that type with [`Op::Coerce`]. Float literals are `f32` unless
+//! they are stored into, or combined with, a variable declared `f64`.
+
+use crate::{CastTarget, Env, Numeric, Value, is_dict_mutation};
+use ferrisscript_compiler::ast::{self, BinaryOp, UnaryOp};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A single VM instruction.
+///
+/// Operands index into the tables of the [`Chunk`] that holds the instruction
+/// (`constants`, `strings`, `places`, `patterns`, `literals`) or name a frame slot.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Op {
+    /// Push `constants[i]`
+    Constant(u32),
+    /// Discard the top of the stack
+    Pop,
+    /// Push a copy of a local slot
+    LoadLocal(u16),
+    /// Pop into a local slot
+    StoreLocal(u16),
+    /// Pop into a local slot, keeping the numeric width of the value it replaces
+    SetLocal(u16),
+    /// Push the global (or host-provided) variable named `strings[i]`
+    LoadGlobal(u32),
+    /// Pop into the existing global `strings[i]`, checking mutability
+    StoreGlobal(u32),
+    /// Pop and define the global `strings[name]`
+    DefineGlobal {
+        name: u32,
+        mutable: bool,
+    },
+    /// Push the value at `places[i]`, consuming its index operands
+    ReadPlace(u32),
+    /// Pop a value and store it at `places[i]`, consuming its index operands
+    StorePlace(u32),
+    /// Pop a value and append it to the array at `places[i]` (`push(arr, v)`)
+    PushPlace(u32),
+    /// Remove the last element of the array at `places[i]` and push it (`pop(arr)`)
+    PopPlace(u32),
+    /// Replace the value on top of the stack with its field `strings[i]`
+    GetField(u32),
+    /// Pop an index and an array, push the element
+    Index,
+    Binary(BinaryOp),
+    Unary(UnaryOp),
+    /// Convert the number (or array of numbers) on top of the stack to a declared type
+    Coerce(Numeric),
+    /// Replace the value on top of the stack with the result of an `as` cast
+    Cast(CastTarget),
+    /// Fail unless the top of the stack is an `i32` (the operand names the value)
+    ExpectInt(&'static str),
+    /// Replace the value on top of the stack with `Ok(value)`
+    MakeOk,
+    /// Replace the value on top of the stack with `Err(value)`
+    MakeErr,
+    /// Replace the `Ok` or `Err` on top of the stack with the value it holds
+    Unwrap,
+    /// Unwrap the `Ok` on top of the stack and jump; leave an `Err` for the
+    /// `Return` that follows (`value?`)
+    Try(u32),
+    /// Pop `n` values, push them as an array
+    MakeArray(u32),
+    /// Pop `n` key-value pairs, push them as a dictionary
+    MakeDict(u32),
+    /// Pop `n` values, push them as a tuple
+    MakeTuple(u32),
+    /// Pop the field values of the struct literal `literals[i]`, push the value
+    MakeStruct(u32),
+    Jump(u32),
+    /// Pop a condition and jump when it is false
+    JumpIfFalse(u32),
+    /// Pop an array or dictionary into slot `array` and reset the index in slot `array + 1`
+    IterArray(u16),
+    /// Advance the range counter in slot `counter` (end in `counter + 1`) into `var`,
+    /// or jump to `exit` once it reaches the end
+    ForRange {
+        counter: u16,
+        var: u16,
+        exit: u32,
+    },
+    /// Advance the array iterator set up by [`Op::IterArray`] into `var`, or jump to
+    /// `exit` after the last element. A dictionary gives its keys.
+    ForArray {
+        array: u16,
+        var: u16,
+        exit: u32,
+    },
+    /// Advance the dictionary iterator set up by [`Op::IterArray`] into `key` and
+    /// `value`, or jump to `exit` after the last entry
+    ForEntry {
+        dict: u16,
+        key: u16,
+        value: u16,
+        exit: u32,
+    },
+    /// Jump to `next` unless the value in `scrutinee` matches `patterns[pattern]`
+    MatchArm {
+        scrutinee: u16,
+        pattern: u32,
+        next: u32,
+    },
+    /// No arm matched the value in the slot
+    NoMatch(u16),
+    /// Call the function at index `function` of the code table
+    Call {
+        function: u32,
+        argc: u16,
+    },
+    /// Call the built-in or script function named `strings[name]`
+    CallNamed {
+        name: u32,
+        argc: u16,
+    },
+    /// Call the function value below the arguments
+    CallValue(u16),
+    /// Push the function at index `i` of the code table as a value
+    LoadFunction(u32),
+    /// Pop the captured values of closure `functions[function]`, push the closure
+    MakeClosure {
+        function: u32,
+        captures: u16,
+    },
+    /// Call method `strings[method]` on the receiver below the arguments. When the
+    /// receiver is a place, its index operands sit below the receiver so that a
+    /// `&mut self` method can write the receiver back.
+    CallMethod {
+        method: u32,
+        argc: u16,
+        place: Option<u32>,
+    },
+    /// Raise the runtime error `strings[i]`
+    Fail(u32),
+    /// Pop the return value and leave the function
+    Return,
+}
+
+/// Root variable of a [`Place`]
+#[derive(Debug, Clone)]
+pub(crate) enum PlaceRoot {
+    Local {
+        slot: u16,
+        name: String,
+        mutable: bool,
+    },
+    Global(String),
+}
+
+/// One step from a place's root towards the value it names
+#[derive(Debug, Clone)]
+pub(crate) enum Step {
+    Field(String),
+    /// Index by the next index operand from the stack
+    Index,
+}
+
+/// A place expression such as `v`, `v[i]`, `v.field` or any chain of them.
+///
+/// Index operands are evaluated onto the stack (root first) before the
+/// instruction that uses the place.
+#[derive(Debug, Clone)]
+pub(crate) struct Place {
+    pub root: PlaceRoot,
+    pub steps: Vec<Step>,
+}
+
+impl Place {
+    pub fn index_count(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| matches!(step, Step::Index))
+            .count()
+    }
+
+    pub fn root_name(&self) -> &str {
+        match &self.root {
+            PlaceRoot::Local { name, .. } => name,
+            PlaceRoot::Global(name) => name,
+        }
+    }
+}
+
+/// Compiled code for one function, method or global initializer list
+#[derive(Debug, Clone)]
+pub(crate) struct Chunk {
+    pub name: String,
+    /// Number of parameters, excluding `self`
+    pub arity: usize,
+    /// Receiver of a method; `self` occupies slot 0 when present
+    pub self_param: Option<ast::SelfParam>,
+    /// Frame slots needed for `self`, parameters, locals and loop/match temporaries
+    pub num_locals: usize,
+    pub code: Vec<Op>,
+    /// Source location of each instruction in `code`, for runtime error messages
+    pub spans: Vec<ast::Span>,
+    pub constants: Vec<Value>,
+    /// Variable, field, function and method names, plus [`Op::Fail`] messages
+    pub strings: Vec<String>,
+    pub places: Vec<Place>,
+    pub patterns: Vec<ast::Pattern>,
+    /// Struct literals: type name and field names in source order
+    pub literals: Vec<(String, Vec<String>)>,
+    /// Closures created by this chunk
+    pub functions: Vec<Rc<Chunk>>,
+}
+
+impl Chunk {
+    /// An empty chunk; also used to reserve a code table entry before compiling
+    pub fn new(name: &str, arity: usize, self_param: Option<ast::SelfParam>) -> Self {
+        Chunk {
+            name: name.to_string(),
+            arity,
+            self_param,
+            num_locals: 0,
+            code: Vec::new(),
+            spans: Vec::new(),
+            constants: Vec::new(),
+            strings: Vec::new(),
+            places: Vec::new(),
+            patterns: Vec::new(),
+            literals: Vec::new(),
+            functions: Vec::new(),
+        }
+    }
+}
+
+struct Local {
+    /// Empty for compiler temporaries, which cannot be named from source
+    name: String,
+    depth: usize,
+    mutable: bool,
+    /// Declared numeric type, if any
+    width: Option<Numeric>,
+}
+
+/// A loop being compiled, for resolving `break` and `continue`
+struct Loop {
+    label: Option<String>,
+    /// Instruction that starts the next iteration
+    start: u32,
+    /// `break` jumps to patch once the loop's exit is known
+    breaks: Vec<usize>,
+}
+
+/// Compiles function bodies against the functions, enums and structs registered in an [`Env`]
+pub(crate) struct Compiler<'a> {
+    env: &'a Env,
+    /// Globals declared by the program being loaded (which are not defined in `env`
+    /// yet) and their declared numeric types
+    globals: &'a HashMap<String, Option<Numeric>>,
+    chunk: Chunk,
+    locals: Vec<Local>,
+    depth: usize,
+    /// Loops enclosing the statement being compiled, innermost last
+    loops: Vec<Loop>,
+    /// Declared numeric return type of the function being compiled
+    return_width: Option<Numeric>,
+    /// Location of the statement or expression being compiled
+    span: ast::Span,
+}
+
+impl<'a> Compiler<'a> {
+    /// Compile a function, or a method when `self_param` is given
+    pub fn compile_function(
+        env: &'a Env,
+        globals: &'a HashMap<String, Option<Numeric>>,
+        func: &ast::Function,
+        self_param: Option<ast::SelfParam>,
+    ) -> Chunk {
+        let mut compiler = Compiler::new(
+            env,
+            globals,
+            Chunk::new(&func.name, func.params.len(), self_param),
+        );
+        compiler.return_width = func.return_type.as_deref().and_then(Numeric::of);
+        if let Some(self_param) = self_param {
+            compiler.declare("self", self_param == ast::SelfParam::RefMut, None);
+        }
+        for param in &func.params {
+            let width = Numeric::of(&param.ty);
+            let slot = compiler.declare(&param.name, true, width);
+            if let Some(width) = width {
+                compiler.emit(Op::LoadLocal(slot));
+                compiler.emit(Op::Coerce(width));
+                compiler.emit(Op::StoreLocal(slot));
+            }
+        }
+        for stmt in &func.body {
+            compiler.stmt(stmt);
+        }
+        compiler.constant(Value::Nil);
+        compiler.emit(Op::Return);
+        compiler.chunk
+    }
+
+    /// Compile the initializers of a program's global variables, in declaration order
+    pub fn compile_globals(
+        env: &'a Env,
+        globals: &'a HashMap<String, Option<Numeric>>,
+        vars: &[ast::GlobalVar],
+    ) -> Chunk {
+        let mut compiler = Compiler::new(env, globals, Chunk::new("<globals>", 0, None));
+        for var in vars {
+            compiler.typed_expr(&var.value, var.ty.as_deref().and_then(Numeric::of));
+            let name = compiler.string(&var.name);
+            compiler.emit(Op::DefineGlobal {
+                name,
+                mutable: var.mutable,
+            });
+        }
+        compiler.constant(Value::Nil);
+        compiler.emit(Op::Return);
+        compiler.chunk
+    }
+
+    fn new(env: &'a Env, globals: &'a HashMap<String, Option<Numeric>>, chunk: Chunk) -> Self {
+        Compiler {
+            env,
+            globals,
+            chunk,
+            locals: Vec::new(),
+            depth: 0,
+            loops: Vec::new(),
+            return_width: None,
+            span: ast::Span::unknown(),
+        }
+    }
+
+    // ---- Emission helpers ----
+
+    fn emit(&mut self, op: Op) -> usize {
+        self.chunk.code.push(op);
+        self.chunk.spans.push(self.span);
+        self.chunk.code.len() - 1
+    }
+
+    fn here(&self) -> u32 {
+        self.chunk.code.len() as u32
+    }
+
+    /// Point the jump emitted at `at` to the next instruction
+    fn patch(&mut self, at: usize) {
+        let target = self.here();
+        match &mut self.chunk.code[at] {
+            Op::Jump(exit)
+            | Op::JumpIfFalse(exit)
+            | Op::Try(exit)
+            | Op::ForRange { exit, .. }
+            | Op::ForArray { exit, .. }
+            | Op::ForEntry { exit, .. }
+            | Op::MatchArm { next: exit, .. } => *exit = target,
+            op => unreachable!("cannot patch {:?}", op),
+        }
+    }
+
+    fn constant(&mut self, value: Value) {
+        self.chunk.constants.push(value);
+        let index = self.chunk.constants.len() as u32 - 1;
+        self.emit(Op::Constant(index));
+    }
+
+    fn string(&mut self, s: &str) -> u32 {
+        if let Some(index) = self.chunk.strings.iter().position(|existing| existing == s) {
+            return index as u32;
+        }
+        self.chunk.strings.push(s.to_string());
+        self.chunk.strings.len() as u32 - 1
+    }
+
+    fn fail(&mut self, message: String) {
+        let index = self.string(&message);
+        self.emit(Op::Fail(index));
+    }
+
+    // ---- Scopes and locals ----
+
+    fn begin_scope(&mut self) {
+        self.depth += 1;
+    }
+
+    fn end_scope(&mut self) {
+        self.depth -= 1;
+        while self
+            .locals
+            .last()
+            .is_some_and(|local| local.depth > self.depth)
+        {
+            self.locals.pop();
+        }
+    }
+
+    fn declare(&mut self, name: &str, mutable: bool, width: Option<Numeric>) -> u16 {
+        self.locals.push(Local {
+            name: name.to_string(),
+            depth: self.depth,
+            mutable,
+            width,
+        });
+        self.chunk.num_locals = self.chunk.num_locals.max(self.locals.len());
+        (self.locals.len() - 1) as u16
+    }
+
+    /// Reserve a slot for a compiler temporary (loop state, match scrutinee)
+    fn temporary(&mut self) -> u16 {
+        self.declare("", true, None)
+    }
+
+    fn resolve(&self, name: &str) -> Option<(u16, bool)> {
+        self.locals
+            .iter()
+            .rposition(|local| local.name == name)
+            .map(|slot| (slot as u16, self.locals[slot].mutable))
+    }
+
+    /// Whether `name` refers to a variable rather than an enum or struct type
+    fn is_variable(&self, name: &str) -> bool {
+        self.resolve(name).is_some()
+            || self.globals.contains_key(name)
+            || self.env.constants.contains_key(name)
+            || self.env.get(name).is_some()
+    }
+
+    /// Declared numeric type of a local or global variable (or a constant)
+    fn variable_width(&self, name: &str) -> Option<Numeric> {
+        match self.resolve(name) {
+            Some((slot, _)) => self.locals[slot as usize].width,
+            None => match self.env.constants.get(name) {
+                Some(value) => Numeric::of_value(value),
+                None => self.globals.get(name).copied().flatten(),
+            },
+        }
+    }
+
+    // ---- Statements ----
+
+    /// Pop the value of a destructuring `let` and bind its parts to new locals,
+    /// skipping `_`
+    fn destructure(&mut self, pattern: &ast::LetPattern, mutable: bool) {
+        let value = self.temporary();
+        self.emit(Op::StoreLocal(value));
+        let parts: Vec<(String, &String)> = match pattern {
+            ast::LetPattern::Tuple(names) => names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| (i.to_string(), name))
+                .collect(),
+            ast::LetPattern::Struct { fields, .. } => fields
+                .iter()
+                .map(|(field, name)| (field.clone(), name))
+                .collect(),
+        };
+        for (field, name) in parts {
+            if name == "_" {
+                continue;
+            }
+            self.emit(Op::LoadLocal(value));
+            let field = self.string(&field);
+            self.emit(Op::GetField(field));
+            let slot = self.declare(name, mutable, None);
+            self.emit(Op::StoreLocal(slot));
+        }
+    }
+
+    fn block(&mut self, stmts: &[ast::Stmt]) {
+        self.begin_scope();
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+        self.end_scope();
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) {
+        let outer = std::mem::replace(&mut self.span, stmt.span());
+        self.compile_stmt(stmt);
+        self.span = outer;
+    }
+
+    fn compile_stmt(&mut self, stmt: &ast::Stmt) {
+        match stmt {
+            ast::Stmt::Let {
+                name,
+                pattern,
+                value,
+                mutable,
+                ty,
+                ..
+            } => {
+                // The initializer still sees any outer variable of the same name
+                let width = ty.as_deref().and_then(Numeric::of);
+                self.typed_expr(value, width);
+                match pattern {
+                    Some(pattern) => self.destructure(pattern, *mutable),
+                    None => {
+                        let slot = self.declare(name, *mutable, width);
+                        self.emit(Op::StoreLocal(slot));
+                    }
+                }
+            }
+
+            ast::Stmt::Assign { target, value, .. } => self.assign(target, value),
+
+            ast::Stmt::If {
+                cond,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.expr(cond);
+                let to_else = self.emit(Op::JumpIfFalse(0));
+                self.block(then_branch);
+                if else_branch.is_empty() {
+                    self.patch(to_else);
+                } else {
+                    let to_end = self.emit(Op::Jump(0));
+                    self.patch(to_else);
+                    self.block(else_branch);
+                    self.patch(to_end);
+                }
+            }
+
+            ast::Stmt::While {
+                label, cond, body, ..
+            } => {
+                let start = self.here();
+                self.expr(cond);
+                let exit = self.emit(Op::JumpIfFalse(0));
+                self.loop_body(label, start, body);
+                self.patch(exit);
+            }
+
+            ast::Stmt::For {
+                label,
+                var,
+                value_var,
+                iterable,
+                body,
+                ..
+            } => self.for_loop(label, var, value_var.as_deref(), iterable, body),
+
+            ast::Stmt::Loop { label, body, .. } => {
+                let start = self.here();
+                self.loop_body(label, start, body);
+            }
+
+            ast::Stmt::Break { label, .. } => {
+                if let Some(target) = self.loop_target("break", label) {
+                    let jump = self.emit(Op::Jump(0));
+                    self.loops[target].breaks.push(jump);
+                }
+            }
+
+            ast::Stmt::Continue { label, .. } => {
+                if let Some(target) = self.loop_target("continue", label) {
+                    let start = self.loops[target].start;
+                    self.emit(Op::Jump(start));
+                }
+            }
+
+            ast::Stmt::Return { value, .. } => {
+                match value {
+                    Some(expr) => self.typed_expr(expr, self.return_width),
+                    None => self.constant(Value::Nil),
+                }
+                self.emit(Op::Return);
+            }
+
+            ast::Stmt::Expr(
+                expr @ (ast::Expr::Match { .. } | ast::Expr::If { .. } | ast::Expr::Block { .. }),
+            ) => self.value(expr, false),
+
+            ast::Stmt::Expr(expr) => {
+                self.expr(expr);
+                self.emit(Op::Pop);
+            }
+        }
+    }
+
+    fn assign(&mut self, target: &ast::Expr, value: &ast::Expr) {
+        match target {
+            ast::Expr::Variable(name, _) => {
+                self.typed_expr(value, self.variable_width(name));
+                match self.resolve(name) {
+                    Some((slot, true)) => {
+                        self.emit(Op::SetLocal(slot));
+                    }
+                    Some((_, false)) => self.fail(format!(
+                        "Error[E400]: Cannot assign to immutable variable '{}'",
+                        name
+                    )),
+                    None if self.env.constants.contains_key(name) => {
+                        self.fail(format!("Error[E400]: Cannot assign to constant '{}'", name))
+                    }
+                    None => {
+                        let name = self.string(name);
+                        self.emit(Op::StoreGlobal(name));
+                    }
+                }
+            }
+            ast::Expr::FieldAccess(..) | ast::Expr::Index(..) => match self.place(target) {
+                Some(place) => {
+                    self.expr(value);
+                    self.emit(Op::StorePlace(place));
+                }
+                None => {
+                    self.expr(value);
+                    self.fail("Error[E426]: Invalid assignment target".to_string());
+                }
+            },
+            _ => {
+                self.expr(value);
+                self.fail("Error[E426]: Invalid assignment target".to_string());
+            }
+        }
+    }
+
+    /// Compile a loop body that jumps back to `start`, followed by the loop's exit
+    /// (which every `break` out of this loop jumps to)
+    fn loop_body(&mut self, label: &Option<String>, start: u32, body: &[ast::Stmt]) {
+        self.loops.push(Loop {
+            label: label.clone(),
+            start,
+            breaks: Vec::new(),
+        });
+        self.block(body);
+        self.emit(Op::Jump(start));
+        let finished = self.loops.pop().expect("loop stack underflow");
+        for jump in finished.breaks {
+            self.patch(jump);
+        }
+    }
+
+    /// Index in `loops` of the loop a `break`/`continue` leaves, innermost unless labeled
+    fn loop_target(&mut self, keyword: &str, label: &Option<String>) -> Option<usize> {
+        let target = match label {
+            Some(label) => self
+                .loops
+                .iter()
+                .rposition(|l| l.label.as_deref() == Some(label.as_str())),
+            None => self.loops.len().checked_sub(1),
+        };
+        if target.is_none() {
+            self.fail(match label {
+                Some(label) => format!("Error[E922]: Undefined loop label '{}'", label),
+                None => format!("Error[E921]: '{}' outside of a loop", keyword),
+            });
+        }
+        target
+    }
+
+    fn for_loop(
+        &mut self,
+        label: &Option<String>,
+        var: &str,
+        value_var: Option<&str>,
+        iterable: &ast::Expr,
+        body: &[ast::Stmt],
+    ) {
+        self.begin_scope();
+
+        // Ranges are iterated without materializing an array
+        let advance = if let ast::Expr::Range(start, end, _) = iterable {
+            let counter = self.temporary();
+            let end_slot = self.temporary();
+            self.expr(start);
+            self.emit(Op::ExpectInt("Range start"));
+            self.emit(Op::StoreLocal(counter));
+            self.expr(end);
+            self.emit(Op::ExpectInt("Range end"));
+            self.emit(Op::StoreLocal(end_slot));
+            let var = self.declare(var, false, None);
+            Op::ForRange {
+                counter,
+                var,
+                exit: 0,
+            }
+        } else {
+            // Iterate over a snapshot so the body may mutate the original collection
+            let array = self.temporary();
+            self.temporary(); // index
+            self.expr(iterable);
+            self.emit(Op::IterArray(array));
+            let var = self.declare(var, false, None);
+            match value_var {
+                Some(value_var) => Op::ForEntry {
+                    dict: array,
+                    key: var,
+                    value: self.declare(value_var, false, None),
+                    exit: 0,
+                },
+                None => Op::ForArray {
+                    array,
+                    var,
+                    exit: 0,
+                },
+            }
+        };
+
+        let start = self.here();
+        let exit = self.emit(advance);
+        self.loop_body(label, start, body);
+        self.patch(exit);
+
+        self.end_scope();
+    }
+
+    /// Compile a match. As a value, every arm leaves exactly one value on the
+    /// stack (`nil` for block arms); as a statement, arms leave nothing.
+    fn match_expr(&mut self, scrutinee: &ast::Expr, arms: &[ast::MatchArm], as_value: bool) {
+        self.begin_scope();
+        self.expr(scrutinee);
+        let slot = self.temporary();
+        self.emit(Op::StoreLocal(slot));
+
+        let mut to_end = Vec::with_capacity(arms.len());
+        for arm in arms {
+            self.chunk.patterns.push(arm.pattern.clone());
+            let pattern = self.chunk.patterns.len() as u32 - 1;
+            let next = self.emit(Op::MatchArm {
+                scrutinee: slot,
+                pattern,
+                next: 0,
+            });
+
+            self.begin_scope();
+            match &arm.pattern {
+                ast::Pattern::Some { binding, .. } if binding != "_" => {
+                    let var = self.declare(binding, false, None);
+                    self.emit(Op::LoadLocal(slot));
+                    self.emit(Op::StoreLocal(var));
+                }
+                ast::Pattern::Ok { binding, .. } | ast::Pattern::Err { binding, .. }
+                    if binding != "_" =>
+                {
+                    let var = self.declare(binding, false, None);
+                    self.emit(Op::LoadLocal(slot));
+                    self.emit(Op::Unwrap);
+                    self.emit(Op::StoreLocal(var));
+                }
+                _ => {}
+            }
+            for stmt in &arm.body {
+                self.stmt(stmt);
+            }
+            self.tail(arm.value.as_ref(), as_value);
+            self.end_scope();
+
+            to_end.push(self.emit(Op::Jump(0)));
+            self.patch(next);
+        }
+        self.emit(Op::NoMatch(slot));
+        for jump in to_end {
+            self.patch(jump);
+        }
+
+        self.end_scope();
+    }
+
+    /// Compile a match, if or block expression. As a value, it leaves exactly
+    /// one value on the stack (`nil` when the branch taken has none); as a
+    /// statement, it leaves nothing.
+    fn value(&mut self, expr: &ast::Expr, as_value: bool) {
+        let outer = std::mem::replace(&mut self.span, expr.span());
+        match expr {
+            ast::Expr::Match {
+                scrutinee, arms, ..
+            } => self.match_expr(scrutinee, arms, as_value),
+            ast::Expr::If {
+                cond,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.expr(cond);
+                let to_else = self.emit(Op::JumpIfFalse(0));
+                self.value(then_branch, as_value);
+                let to_end = self.emit(Op::Jump(0));
+                self.patch(to_else);
+                match else_branch {
+                    Some(else_branch) => self.value(else_branch, as_value),
+                    None => self.tail(None, as_value),
+                }
+                self.patch(to_end);
+            }
+            ast::Expr::Block { stmts, value, .. } => {
+                self.begin_scope();
+                for stmt in stmts {
+                    self.stmt(stmt);
+                }
+                self.tail(value.as_deref(), as_value);
+                self.end_scope();
+            }
+            _ => {
+                self.compile_expr(expr);
+                if !as_value {
+                    self.emit(Op::Pop);
+                }
+            }
+        }
+        self.span = outer;
+    }
+
+    /// Compile the final value expression of a block or match arm
+    fn tail(&mut self, value: Option<&ast::Expr>, as_value: bool) {
+        match value {
+            Some(value) => self.value(value, as_value),
+            None if as_value => self.constant(Value::Nil),
+            None => {}
+        }
+    }
+
+    // ---- Expressions ----
+
+    fn expr(&mut self, expr: &ast::Expr) {
+        let outer = std::mem::replace(&mut self.span, expr.span());
+        self.compile_expr(expr);
+        self.span = outer;
+    }
+
+    fn compile_expr(&mut self, expr: &ast::Expr) {
+        match expr {
+            ast::Expr::Literal(lit, _) => match lit {
+                ast::Literal::Int(i) => self.int_constant(*i),
+                ast::Literal::Float(f) => self.constant(Value::Float(*f as f32)),
+                ast::Literal::Bool(b) => self.constant(Value::Bool(*b)),
+                ast::Literal::Str(s) => self.constant(Value::String(s.clone())),
+            },
+
+            ast::Expr::Variable(name, _) => self.variable(name),
+
+            ast::Expr::Binary(left, op, right, _) => {
+                // Float literals combined with an f64 variable are f64 literals
+                let width = self
+                    .operand_width(left)
+                    .or(self.operand_width(right))
+                    .filter(|width| *width == Numeric::F64);
+                self.operand(left, width);
+                self.operand(right, width);
+                self.emit(Op::Binary(*op));
+            }
+
+            ast::Expr::Unary(op, operand, _) => match (op, &**operand) {
+                // `-2147483648` is the i32 minimum, not the negation of an i64
+                (UnaryOp::Neg, ast::Expr::Literal(ast::Literal::Int(i), _)) => {
+                    self.int_constant(-i)
+                }
+                _ => {
+                    self.expr(operand);
+                    self.emit(Op::Unary(*op));
+                }
+            },
+
+            ast::Expr::Call(name, args, _) if name == "push" || name == "pop" => {
+                self.array_mutation(name, args)
+            }
+
+            // `Some(value)` is represented by the value itself
+            ast::Expr::Call(name, args, _) if name == "Some" && args.len() == 1 => {
+                self.expr(&args[0]);
+            }
+
+            ast::Expr::Call(name, args, _)
+                if (name == "Ok" || name == "Err") && args.len() == 1 =>
+            {
+                self.expr(&args[0]);
+                self.emit(if name == "Ok" {
+                    Op::MakeOk
+                } else {
+                    Op::MakeErr
+                });
+            }
+
+            ast::Expr::Try(operand, _) => {
+                self.expr(operand);
+                let to_ok = self.emit(Op::Try(0));
+                self.emit(Op::Return);
+                self.patch(to_ok);
+            }
+
+            // A call through a variable holding a function or closure
+            ast::Expr::Call(name, args, _)
+                if !self.env.is_builtin(name)
+                    && !self.env.functions.contains_key(name)
+                    && self.is_variable(name) =>
+            {
+                self.variable(name);
+                for arg in args {
+                    self.expr(arg);
+                }
+                self.emit(Op::CallValue(args.len() as u16));
+            }
+
+            ast::Expr::Call(name, args, _) => {
+                let params = if self.env.is_builtin(name) {
+                    None
+                } else {
+                    self.env.functions.get(name).map(|func| &func.params)
+                };
+                for (i, arg) in args.iter().enumerate() {
+                    let width = params
+                        .and_then(|params| params.get(i))
+                        .and_then(|param| Numeric::of(&param.ty));
+                    self.operand(arg, width);
+                }
+                let argc = args.len() as u16;
+                // Built-ins take precedence over script functions, as in `call_function`
+                match self.env.function_ids.get(name) {
+                    Some(&id) if !self.env.is_builtin(name) => {
+                        self.emit(Op::Call {
+                            function: id as u32,
+                            argc,
+                        });
+                    }
+                    _ => {
+                        let name = self.string(name);
+                        self.emit(Op::CallNamed { name, argc });
+                    }
+                }
+            }
+
+            ast::Expr::FieldAccess(object, field, _) => {
+                // `EnumName.Variant` (unless a variable shadows the enum name)
+                if let ast::Expr::Variable(name, _) = &**object
+                    && !self.is_variable(name)
+                    && self.env.has_enum(name)
+                {
+                    self.constant(Value::Enum {
+                        enum_name: name.clone(),
+                        variant: field.clone(),
+                    });
+                } else if let Some(place) = self.place(expr) {
+                    self.emit(Op::ReadPlace(place));
+                } else {
+                    self.expr(object);
+                    let field = self.string(field);
+                    self.emit(Op::GetField(field));
+                }
+            }
+
+            ast::Expr::Index(array, index, _) => {
+                if let Some(place) = self.place(expr) {
+                    self.emit(Op::ReadPlace(place));
+                } else {
+                    self.expr(array);
+                    self.expr(index);
+                    self.emit(Op::Index);
+                }
+            }
+
+            ast::Expr::StructLiteral {
+                type_name, fields, ..
+            } => {
+                for (field, value) in fields {
+                    self.operand(value, self.env.field_width(type_name, field));
+                }
+                let names = fields.iter().map(|(name, _)| name.clone()).collect();
+                self.chunk.literals.push((type_name.clone(), names));
+                let literal = self.chunk.literals.len() as u32 - 1;
+                self.emit(Op::MakeStruct(literal));
+            }
+
+            ast::Expr::ArrayLiteral(elements, _) => {
+                for element in elements {
+                    self.expr(element);
+                }
+                self.emit(Op::MakeArray(elements.len() as u32));
+            }
+
+            ast::Expr::DictLiteral(entries, _) => {
+                for (key, value) in entries {
+                    self.expr(key);
+                    self.expr(value);
+                }
+                self.emit(Op::MakeDict(entries.len() as u32));
+            }
+
+            ast::Expr::Tuple(elements, _) => {
+                for element in elements {
+                    self.expr(element);
+                }
+                self.emit(Op::MakeTuple(elements.len() as u32));
+            }
+
+            ast::Expr::Match { .. } | ast::Expr::If { .. } | ast::Expr::Block { .. } => {
+                self.value(expr, true)
+            }
+
+            ast::Expr::MethodCall {
+                receiver,
+                method,
+                args,
+                ..
+            } => self.method_call(receiver, method, args),
+
+            // `for` loops iterate ranges directly; nothing else may hold one
+            ast::Expr::Range(..) => {
+                self.fail("Error[E923]: Range used outside of a for loop".to_string());
+            }
+
+            ast::Expr::Cast(operand, ty, _) => {
+                // `0.1 as f64` reads the literal at full precision
+                let target = CastTarget::of(ty);
+                let width = (target == Some(CastTarget::F64)).then_some(Numeric::F64);
+                self.operand(operand, width);
+                match target {
+                    Some(to) => {
+                        self.emit(Op::Cast(to));
+                    }
+                    None => self.fail(format!("Error[E422]: Invalid cast: cannot cast to {}", ty)),
+                }
+            }
+
+            ast::Expr::Lambda { params, body, .. } => self.lambda(params, body),
+
+            // Compound assignment and regular assignment expressions are desugared
+            // to Stmt::Assign at parse time
+            ast::Expr::Assign(..) | ast::Expr::CompoundAssign(..) => {
+                self.fail("Error[E418]: Assignment expressions should be statements".to_string())
+            }
+        }
+    }
+
+    /// Compile `expr` as a value stored with the declared numeric type `width`
+    fn typed_expr(&mut self, expr: &ast::Expr, width: Option<Numeric>) {
+        self.operand(expr, width);
+        if let Some(width) = width {
+            self.emit(Op::Coerce(width));
+        }
+    }
+
+    /// Compile `expr`, reading float literals in it at `width` when that is `f64`
+    /// so that they keep their full precision
+    fn operand(&mut self, expr: &ast::Expr, width: Option<Numeric>) {
+        match (expr, width) {
+            (ast::Expr::Literal(ast::Literal::Float(f), _), Some(Numeric::F64)) => {
+                self.constant(Value::Float64(*f))
+            }
+            (ast::Expr::Unary(UnaryOp::Neg, operand, _), Some(Numeric::F64)) => {
+                self.operand(operand, width);
+                self.emit(Op::Unary(UnaryOp::Neg));
+            }
+            (ast::Expr::Binary(left, op, right, _), Some(Numeric::F64))
+                if matches!(
+                    op,
+                    BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem
+                ) =>
+            {
+                self.operand(left, width);
+                self.operand(right, width);
+                self.emit(Op::Binary(*op));
+            }
+            _ => self.expr(expr),
+        }
+    }
+
+    /// Declared numeric type of an operand that names a variable
+    fn operand_width(&self, expr: &ast::Expr) -> Option<Numeric> {
+        match expr {
+            ast::Expr::Variable(name, _) => self.variable_width(name),
+            _ => None,
+        }
+    }
+
+    /// Push the value of a variable: a local, a constant, a global or, when no
+    /// variable has the name, the script function it names
+    fn variable(&mut self, name: &str) {
+        match self.resolve(name) {
+            Some((slot, _)) => {
+                self.emit(Op::LoadLocal(slot));
+            }
+            None if let Some(value) = self.env.constants.get(name) => {
+                self.constant(value.clone());
+            }
+            None if !self.is_variable(name)
+                && let Some(&id) = self.env.function_ids.get(name) =>
+            {
+                self.emit(Op::LoadFunction(id as u32));
+            }
+            None if name == "None" && !self.is_variable(name) => {
+                self.constant(Value::Nil);
+            }
+            None => {
+                let name = self.string(name);
+                self.emit(Op::LoadGlobal(name));
+            }
+        }
+    }
+
+    /// Compile a closure into a nested chunk and push the closure, capturing the
+    /// current values of the enclosing locals its body refers to
+    fn lambda(&mut self, params: &[(String, Option<String>)], body: &ast::Expr) {
+        let mut names = Vec::new();
+        expr_names(body, &mut names);
+        let mut captures: Vec<(&String, u16)> = Vec::new();
+        for &name in &names {
+            if params.iter().any(|(param, _)| param == name)
+                || captures.iter().any(|&(captured, _)| captured == name)
+            {
+                continue;
+            }
+            if let Some((slot, _)) = self.resolve(name) {
+                captures.push((name, slot));
+            }
+        }
+
+        let mut compiler = Compiler::new(
+            self.env,
+            self.globals,
+            Chunk::new("<closure>", params.len(), None),
+        );
+        compiler.span = body.span();
+        for (name, ty) in params {
+            let width = ty.as_deref().and_then(Numeric::of);
+            let slot = compiler.declare(name, true, width);
+            if let Some(width) = width {
+                compiler.emit(Op::LoadLocal(slot));
+                compiler.emit(Op::Coerce(width));
+                compiler.emit(Op::StoreLocal(slot));
+            }
+        }
+        for &(name, slot) in &captures {
+            let width = self.locals[slot as usize].width;
+            compiler.declare(name, false, width);
+        }
+        compiler.value(body, true);
+        compiler.emit(Op::Return);
+
+        self.chunk.functions.push(Rc::new(compiler.chunk));
+        let function = self.chunk.functions.len() as u32 - 1;
+        for (_, slot) in &captures {
+            self.emit(Op::LoadLocal(*slot));
+        }
+        self.emit(Op::MakeClosure {
+            function,
+            captures: captures.len() as u16,
+        });
+    }
+
+    /// Push an integer literal as an `i32` when it fits
+    fn int_constant(&mut self, i: i64) {
+        self.constant(match i32::try_from(i) {
+            Ok(i) => Value::Int(i),
+            Err(_) => Value::Int64(i),
+        });
+    }
+
+    /// Compile `push(arr, value)` / `pop(arr)`, which mutate their array argument in place
+    fn array_mutation(&mut self, name: &str, args: &[ast::Expr]) {
+        match (name, args) {
+            ("push", [array, value]) => match self.place(array) {
+                Some(place) => {
+                    self.expr(value);
+                    self.emit(Op::PushPlace(place));
+                }
+                None => self.fail("Error[E426]: Invalid assignment target".to_string()),
+            },
+            ("pop", [array]) => match self.place(array) {
+                Some(place) => {
+                    self.emit(Op::PopPlace(place));
+                }
+                None => self.fail("Error[E426]: Invalid assignment target".to_string()),
+            },
+            _ => self.fail(format!(
+                "Error[E416]: Function {} expects {} arguments, got {}",
+                name,
+                if name == "push" { 2 } else { 1 },
+                args.len()
+            )),
+        }
+    }
+
+    /// Compile `receiver.method(args)` or the associated function call `StructName.function(args)`
+    fn method_call(&mut self, receiver: &ast::Expr, method: &str, args: &[ast::Expr]) {
+        if let ast::Expr::Variable(name, _) = receiver
+            && !self.is_variable(name)
+            && self.env.has_struct(name)
+        {
+            for arg in args {
+                self.expr(arg);
+            }
+            match self.env.method_id(name, method) {
+                Some(id) => {
+                    self.emit(Op::Call {
+                        function: id as u32,
+                        argc: args.len() as u16,
+                    });
+                }
+                None => self.fail(format!(
+                    "Error[E415]: Undefined function: {}.{}",
+                    name, method
+                )),
+            }
+            return;
+        }
+
+        // Only a `&mut self` method or a dictionary mutation needs to know where
+        // its receiver lives
+        let place = if self.env.has_mut_method(method) || is_dict_mutation(method) {
+            self.place(receiver)
+        } else {
+            None
+        };
+        self.expr(receiver);
+        for arg in args {
+            self.expr(arg);
+        }
+        let method = self.string(method);
+        self.emit(Op::CallMethod {
+            method,
+            argc: args.len() as u16,
+            place,
+        });
+    }
+
+    /// If `expr` is a place rooted at a variable, emit its index operands and
+    /// return the index of its [`Place`]. Emits nothing for other expressions.
+    fn place(&mut self, expr: &ast::Expr) -> Option<u32> {
+        let mut steps = Vec::new();
+        let mut indices = Vec::new();
+        let mut root = expr;
+        let name = loop {
+            match root {
+                ast::Expr::Index(array, index, _) => {
+                    steps.push(Step::Index);
+                    indices.push(&**index);
+                    root = array;
+                }
+                ast::Expr::FieldAccess(object, field, _) => {
+                    steps.push(Step::Field(field.clone()));
+                    root = object;
+                }
+                ast::Expr::Variable(name, _) => break name,
+                _ => return None,
+            }
+        };
+
+        let root = match self.resolve(name) {
+            Some((slot, mutable)) => PlaceRoot::Local {
+                slot,
+                name: name.clone(),
+                mutable,
+            },
+            None if !self.is_variable(name)
+                && (self.env.has_enum(name) || self.env.has_struct(name)) =>
+            {
+                return None;
+            }
+            None => PlaceRoot::Global(name.clone()),
+        };
+
+        steps.reverse();
+        for index in indices.into_iter().rev() {
+            self.expr(index);
+        }
+        self.chunk.places.push(Place { root, steps });
+        Some(self.chunk.places.len() as u32 - 1)
+    }
+}
+
+/// Collect the names an expression refers to: variables, and functions called
+/// by name (which may be variables holding closures), including inside nested
+/// closures. Names may repeat.
+fn expr_names<'e>(expr: &'e ast::Expr, names: &mut Vec<&'e String>) {
+    match expr {
+        ast::Expr::Literal(..) => {}
+        ast::Expr::Variable(name, _) => names.push(name),
+        ast::Expr::Call(name, args, _) => {
+            names.push(name);
+            for arg in args {
+                expr_names(arg, names);
+            }
+        }
+        ast::Expr::Binary(left, _, right, _)
+        | ast::Expr::Assign(left, right, _)
+        | ast::Expr::CompoundAssign(left, _, right, _)
+        | ast::Expr::Index(left, right, _)
+        | ast::Expr::Range(left, right, _) => {
+            expr_names(left, names);
+            expr_names(right, names);
+        }
+        ast::Expr::Unary(_, operand, _)
+        | ast::Expr::FieldAccess(operand, _, _)
+        | ast::Expr::Cast(operand, _, _)
+        | ast::Expr::Try(operand, _) => expr_names(operand, names),
+        ast::Expr::StructLiteral { fields, .. } => {
+            for (_, value) in fields {
+                expr_names(value, names);
+            }
+        }
+        ast::Expr::ArrayLiteral(elements, _) | ast::Expr::Tuple(elements, _) => {
+            for element in elements {
+                expr_names(element, names);
+            }
+        }
+        ast::Expr::DictLiteral(entries, _) => {
+            for (key, value) in entries {
+                expr_names(key, names);
+                expr_names(value, names);
+            }
+        }
+        ast::Expr::Match {
+            scrutinee, arms, ..
+        } => {
+            expr_names(scrutinee, names);
+            for arm in arms {
+                stmts_names(&arm.body, names);
+                if let Some(value) = &arm.value {
+                    expr_names(value, names);
+                }
+            }
+        }
+        ast::Expr::MethodCall { receiver, args, .. } => {
+            expr_names(receiver, names);
+            for arg in args {
+                expr_names(arg, names);
+            }
+        }
+        ast::Expr::If {
+            cond,
+            then_branch,
+            else_branch,
+            ..
+        } => {
+            expr_names(cond, names);
+            expr_names(then_branch, names);
+            if let Some(else_branch) = else_branch {
+                expr_names(else_branch, names);
+            }
+        }
+        ast::Expr::Block { stmts, value, .. } => {
+            stmts_names(stmts, names);
+            if let Some(value) = value {
+                expr_names(value, names);
+            }
+        }
+        ast::Expr::Lambda { body, .. } => expr_names(body, names),
+    }
+}
+
+/// Collect the names a list of statements refers to, as in [`expr_names`]
+fn stmts_names<'e>(stmts: &'e [ast::Stmt], names: &mut Vec<&'e String>) {
+    for stmt in stmts {
+        match stmt {
+            ast::Stmt::Expr(expr) | ast::Stmt::Let { value: expr, .. } => expr_names(expr, names),
+            ast::Stmt::Assign { target, value, .. } => {
+                expr_names(target, names);
+                expr_names(value, names);
+            }
+            ast::Stmt::If {
+                cond,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                expr_names(cond, names);
+                stmts_names(then_branch, names);
+                stmts_names(else_branch, names);
+            }
+            ast::Stmt::While { cond, body, .. } => {
+                expr_names(cond, names);
+                stmts_names(body, names);
+            }
+            ast::Stmt::For { iterable, body, .. } => {
+                expr_names(iterable, names);
+                stmts_names(body, names);
+            }
+            ast::Stmt::Loop { body, .. } => stmts_names(body, names),
+            ast::Stmt::Return { value, .. } => {
+                if let Some(value) = value {
+                    expr_names(value, names);
+                }
+            }
+            ast::Stmt::Break { .. } | ast::Stmt::Continue { .. } => {}
+        }
+    }
+}
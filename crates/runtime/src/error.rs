@@ -0,0 +1,332 @@
+//! Runtime errors with an error code, source location and FerrisScript call stack.
+//!
+//! Errors are raised deep inside the runtime as `"Error[EXXX]: message"` strings;
+//! the VM turns them into a [`RuntimeError`] and records where execution stopped
+//! and which script functions were running.
+
+use ferrisscript_compiler::ast::{SourceFile, Span};
+use ferrisscript_compiler::error_code::ErrorCode;
+use ferrisscript_compiler::error_context::{
+    extract_source_context_with_pointer, format_error_with_code,
+};
+use ferrisscript_compiler::span::FileId;
+use std::fmt;
+
+/// A FerrisScript function that was running when an error occurred
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackFrame {
+    /// Function or method name (`<globals>` for global initializers)
+    pub function: String,
+    /// The failing expression in the innermost frame, or the call into the
+    /// next frame in the others
+    pub span: Span,
+}
+
+/// An error raised while running a FerrisScript program
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeError {
+    /// Error code, if the error has one
+    pub code: Option<ErrorCode>,
+    /// Message without the `Error[EXXX]: ` prefix
+    pub message: String,
+    /// Location of the failing expression; unknown for errors raised before
+    /// any script code ran, such as calling an undefined function
+    pub span: Span,
+    /// Script functions that were running, innermost first
+    pub call_stack: Vec<StackFrame>,
+}
+
+impl RuntimeError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        RuntimeError {
+            code: Some(code),
+            message: message.into(),
+            span: Span::unknown(),
+            call_stack: Vec::new(),
+        }
+    }
+
+    /// Format the error with the offending source line, like compile errors.
+    ///
+    /// `source` must be the script the failing program was compiled from.
+    pub fn format_with_source(&self, source: &str) -> String {
+        self.report(source, &[])
+    }
+
+    /// Format the error with the offending source line from whichever of the
+    /// program's `files` it occurred in, naming the file when it is a module.
+    pub fn format_with_files(&self, files: &[SourceFile]) -> String {
+        match files.get(self.span.file.0 as usize) {
+            Some(file) => self.report(&file.source, files),
+            None => format!("{}{}", self.summary(files), self.stack_trace(files)),
+        }
+    }
+
+    fn report(&self, source: &str, files: &[SourceFile]) -> String {
+        let summary = self.summary(files);
+        if self.span.is_unknown() {
+            return format!("{}{}", summary, self.stack_trace(files));
+        }
+        let (line, column) = (self.span.line(), self.span.column());
+        let report = match self.code {
+            Some(code) => {
+                format_error_with_code(code, &summary, source, line, column, &self.message)
+            }
+            None => format!(
+                "{}\n\n{}",
+                summary,
+                extract_source_context_with_pointer(source, line, Some(column), &self.message)
+            ),
+        };
+        format!("{}{}", report, self.stack_trace(files))
+    }
+
+    /// The message with its code and location, e.g.
+    /// `Error[E413]: Division by zero at line 3, column 20`
+    fn summary(&self, files: &[SourceFile]) -> String {
+        let mut summary = match self.code {
+            Some(code) => format!("Error[{}]: {}", code.as_str(), self.message),
+            None => self.message.clone(),
+        };
+        if !self.span.is_unknown() {
+            summary.push_str(&format!(
+                " at line {}, column {}",
+                self.span.line(),
+                self.span.column()
+            ));
+            if let Some(path) = module_path(self.span, files) {
+                summary.push_str(&format!(" in {}", path));
+            }
+        }
+        summary
+    }
+
+    /// One line per call stack frame, innermost first.
+    ///
+    /// Runs of identical frames, as left by runaway recursion, are collapsed
+    /// into their first frame and a count, and at most
+    /// [`MAX_TRACE_FRAMES`] lines are kept from each end of what remains.
+    fn stack_trace(&self, files: &[SourceFile]) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        let mut frames = self.call_stack.iter().peekable();
+        while let Some(frame) = frames.next() {
+            let mut repeats = 0;
+            while frames.next_if(|next| *next == frame).is_some() {
+                repeats += 1;
+            }
+            lines.push(frame_line(frame, files));
+            if repeats > 0 {
+                lines.push(format!(
+                    "\n    ... {} more call{} to {}",
+                    repeats,
+                    if repeats == 1 { "" } else { "s" },
+                    frame.function
+                ));
+            }
+        }
+
+        if lines.len() > 2 * MAX_TRACE_FRAMES {
+            let omitted = lines.len() - 2 * MAX_TRACE_FRAMES;
+            lines.splice(
+                MAX_TRACE_FRAMES..lines.len() - MAX_TRACE_FRAMES,
+                [format!("\n    ... {} more frames", omitted)],
+            );
+        }
+        lines.concat()
+    }
+}
+
+/// Stack trace lines kept from each end of a long call stack
+const MAX_TRACE_FRAMES: usize = 10;
+
+/// The stack trace line for one frame
+fn frame_line(frame: &StackFrame, files: &[SourceFile]) -> String {
+    if frame.span.is_unknown() {
+        return format!("\n    at {}", frame.function);
+    }
+    match module_path(frame.span, files) {
+        Some(path) => format!(
+            "\n    at {} ({}, line {})",
+            frame.function,
+            path,
+            frame.span.line()
+        ),
+        None => format!("\n    at {} (line {})", frame.function, frame.span.line()),
+    }
+}
+
+/// Path of the module a span is in; `None` in the script itself
+fn module_path(span: Span, files: &[SourceFile]) -> Option<&str> {
+    if span.file == FileId::MAIN {
+        return None;
+    }
+    files
+        .get(span.file.0 as usize)
+        .map(|file| file.path.as_str())
+}
+
+/// Parse a runtime error message, splitting off a leading `Error[EXXX]: ` code
+impl From<String> for RuntimeError {
+    fn from(message: String) -> Self {
+        let code = message
+            .strip_prefix("Error[")
+            .and_then(|rest| rest.split_once("]: "))
+            .and_then(|(code, rest)| Some((ErrorCode::from_code(code)?, rest)));
+        match code {
+            Some((code, rest)) => RuntimeError::new(code, rest),
+            None => RuntimeError {
+                code: None,
+                message,
+                span: Span::unknown(),
+                call_stack: Vec::new(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.summary(&[]), self.stack_trace(&[]))
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrisscript_compiler::span::Position;
+
+    fn at(line: usize, column: usize) -> Span {
+        Span::point(Position::new(line, column, 0))
+    }
+
+    #[test]
+    fn test_code_is_split_from_message() {
+        let error = RuntimeError::from("Error[E413]: Division by zero".to_string());
+        assert_eq!(error.code, Some(ErrorCode::E413));
+        assert_eq!(error.message, "Division by zero");
+        assert_eq!(error.to_string(), "Error[E413]: Division by zero");
+
+        // Messages without a known code are kept whole
+        let error = RuntimeError::from("Node not found".to_string());
+        assert_eq!(error.code, None);
+        assert_eq!(error.to_string(), "Node not found");
+        let error = RuntimeError::from("Error[E604]: No Godot context".to_string());
+        assert_eq!(error.code, None);
+        assert_eq!(error.to_string(), "Error[E604]: No Godot context");
+    }
+
+    #[test]
+    fn test_display_includes_location_and_call_stack() {
+        let mut error = RuntimeError::new(ErrorCode::E413, "Division by zero");
+        error.span = at(3, 12);
+        error.call_stack = vec![
+            StackFrame {
+                function: "ratio".to_string(),
+                span: at(3, 12),
+            },
+            StackFrame {
+                function: "_ready".to_string(),
+                span: at(7, 5),
+            },
+        ];
+        assert_eq!(
+            error.to_string(),
+            "Error[E413]: Division by zero at line 3, column 12\n    at ratio (line 3)\n    at _ready (line 7)"
+        );
+    }
+
+    #[test]
+    fn test_stack_trace_collapses_repeated_frames() {
+        let frame = |function: &str, line| StackFrame {
+            function: function.to_string(),
+            span: at(line, 5),
+        };
+        let mut error = RuntimeError::new(ErrorCode::E403, "Stack overflow");
+        error.call_stack = vec![frame("rec", 2); 1023];
+        error.call_stack.push(frame("_ready", 6));
+        assert_eq!(
+            error.to_string(),
+            "Error[E403]: Stack overflow\n    at rec (line 2)\n    ... 1022 more calls to rec\n    at _ready (line 6)"
+        );
+
+        // Mutual recursion does not repeat a single frame, so only both ends of
+        // the trace are kept
+        error.call_stack = (0..100)
+            .map(|i| frame(if i % 2 == 0 { "ping" } else { "pong" }, 2 + i % 2))
+            .collect();
+        let trace = error.to_string();
+        assert_eq!(
+            trace.lines().count(),
+            1 + 2 * MAX_TRACE_FRAMES + 1,
+            "{}",
+            trace
+        );
+        assert!(trace.contains("\n    ... 80 more frames\n"), "{}", trace);
+        assert!(trace.ends_with("    at pong (line 3)"), "{}", trace);
+    }
+
+    #[test]
+    fn test_format_with_source_points_at_line() {
+        let source = "fn ratio(a: i32, b: i32) -> i32 {\n    return a / b;\n}";
+        let mut error = RuntimeError::new(ErrorCode::E413, "Division by zero");
+        error.span = at(2, 12);
+        let report = error.format_with_source(source);
+        assert!(
+            report.starts_with("Error[E413]: Division by zero\n"),
+            "{}",
+            report
+        );
+        assert!(report.contains(" 2 |     return a / b;"), "{}", report);
+        assert!(report.contains("^ Division by zero"), "{}", report);
+
+        // Without a location there is no source line to show
+        let error = RuntimeError::new(ErrorCode::E415, "Undefined function: missing");
+        assert_eq!(
+            error.format_with_source(source),
+            "Error[E415]: Undefined function: missing"
+        );
+    }
+
+    #[test]
+    fn test_format_with_files_names_module() {
+        let files = vec![
+            SourceFile {
+                path: "res://player.ferris".to_string(),
+                source: "use \"lib/stats.ferris\";\nfn _ready() {\n    stats.ratio(1, 0);\n}"
+                    .to_string(),
+            },
+            SourceFile {
+                path: "res://lib/stats.ferris".to_string(),
+                source: "fn ratio(a: i32, b: i32) -> i32 {\n    return a / b;\n}".to_string(),
+            },
+        ];
+        let mut error = RuntimeError::new(ErrorCode::E413, "Division by zero");
+        error.span = at(2, 12).in_file(FileId(1));
+        error.call_stack = vec![
+            StackFrame {
+                function: "stats.ratio".to_string(),
+                span: at(2, 12).in_file(FileId(1)),
+            },
+            StackFrame {
+                function: "_ready".to_string(),
+                span: at(3, 5),
+            },
+        ];
+        let report = error.format_with_files(&files);
+        assert!(
+            report.contains("Division by zero at line 2, column 12 in res://lib/stats.ferris"),
+            "{}",
+            report
+        );
+        assert!(report.contains(" 2 |     return a / b;"), "{}", report);
+        assert!(
+            report.ends_with(
+                "    at stats.ratio (res://lib/stats.ferris, line 2)\n    at _ready (line 3)"
+            ),
+            "{}",
+            report
+        );
+    }
+}
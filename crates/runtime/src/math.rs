@@ -0,0 +1,231 @@
+//! Math built-ins: trigonometry, rounding, `abs`/`min`/`max`/`clamp`, `lerp`,
+//! and the `PI` and `TAU` constants.
+//!
+//! The type checker resolves each call to one overload but does not convert the
+//! arguments, so numbers arrive here in their own representations and are
+//! promoted like arithmetic operands: integers stay integers where the result
+//! is exact (`abs`, `min`, `max`, `clamp`), an `i32` is read as an `f32`, and an
+//! `i64` or `f64` makes the result an `f64`. Rounding, `abs`, `min`, `max`,
+//! `clamp` and `lerp` also work per component on `Vector2`.
+
+use crate::{BuiltinFn, Env, Operands, Value};
+
+/// Math functions by name
+const FUNCTIONS: &[(&str, BuiltinFn)] = &[
+    ("sin", sin),
+    ("cos", cos),
+    ("tan", tan),
+    ("sqrt", sqrt),
+    ("floor", floor),
+    ("ceil", ceil),
+    ("round", round),
+    ("atan2", atan2),
+    ("pow", pow),
+    ("abs", abs),
+    ("min", min),
+    ("max", max),
+    ("clamp", clamp),
+    ("lerp", lerp),
+];
+
+/// Register the math functions and constants in a new [`Env`]
+pub(crate) fn register(env: &mut Env) {
+    for (name, func) in FUNCTIONS {
+        env.builtin_fns.insert(name.to_string(), *func);
+    }
+    env.constants
+        .insert("PI".to_string(), Value::Float(std::f32::consts::PI));
+    env.constants
+        .insert("TAU".to_string(), Value::Float(std::f32::consts::TAU));
+}
+
+/// Whether `name` is a math function (which a script function of the same name shadows)
+pub(crate) fn is_math_function(name: &str) -> bool {
+    FUNCTIONS.iter().any(|(function, _)| *function == name)
+}
+
+fn expects(name: &str, what: &str) -> String {
+    format!("{}() expects {}", name, what)
+}
+
+/// Apply a float function to one number, at `f64` precision for `i64`/`f64`
+fn float_fn(
+    name: &str,
+    args: &[Value],
+    f32_fn: fn(f32) -> f32,
+    f64_fn: fn(f64) -> f64,
+) -> Result<Value, String> {
+    match args {
+        [Value::Int64(i)] => Ok(Value::Float64(f64_fn(*i as f64))),
+        [Value::Float64(f)] => Ok(Value::Float64(f64_fn(*f))),
+        [value] => value
+            .to_float()
+            .map(|x| Value::Float(f32_fn(x)))
+            .ok_or_else(|| expects(name, "a number")),
+        _ => Err(expects(name, "a single number")),
+    }
+}
+
+/// Like [`float_fn`], also applied per component to a `Vector2`
+fn rounding_fn(
+    name: &str,
+    args: &[Value],
+    f32_fn: fn(f32) -> f32,
+    f64_fn: fn(f64) -> f64,
+) -> Result<Value, String> {
+    match args {
+        [Value::Vector2 { x, y }] => Ok(Value::Vector2 {
+            x: f32_fn(*x),
+            y: f32_fn(*y),
+        }),
+        _ => float_fn(name, args, f32_fn, f64_fn),
+    }
+}
+
+/// Apply a float function to two numbers
+fn float_fn2(
+    name: &str,
+    args: &[Value],
+    f32_fn: fn(f32, f32) -> f32,
+    f64_fn: fn(f64, f64) -> f64,
+) -> Result<Value, String> {
+    let [a, b] = args else {
+        return Err(expects(name, "two numbers"));
+    };
+    match Operands::promote(a, b) {
+        Some(Operands::Int(a, b)) => Ok(Value::Float(f32_fn(a as f32, b as f32))),
+        Some(Operands::Float(a, b)) => Ok(Value::Float(f32_fn(a, b))),
+        Some(Operands::Int64(a, b)) => Ok(Value::Float64(f64_fn(a as f64, b as f64))),
+        Some(Operands::Float64(a, b)) => Ok(Value::Float64(f64_fn(a, b))),
+        None => Err(expects(name, "two numbers")),
+    }
+}
+
+fn sin(args: &[Value]) -> Result<Value, String> {
+    float_fn("sin", args, f32::sin, f64::sin)
+}
+
+fn cos(args: &[Value]) -> Result<Value, String> {
+    float_fn("cos", args, f32::cos, f64::cos)
+}
+
+fn tan(args: &[Value]) -> Result<Value, String> {
+    float_fn("tan", args, f32::tan, f64::tan)
+}
+
+fn sqrt(args: &[Value]) -> Result<Value, String> {
+    float_fn("sqrt", args, f32::sqrt, f64::sqrt)
+}
+
+fn floor(args: &[Value]) -> Result<Value, String> {
+    rounding_fn("floor", args, f32::floor, f64::floor)
+}
+
+fn ceil(args: &[Value]) -> Result<Value, String> {
+    rounding_fn("ceil", args, f32::ceil, f64::ceil)
+}
+
+fn round(args: &[Value]) -> Result<Value, String> {
+    rounding_fn("round", args, f32::round, f64::round)
+}
+
+fn atan2(args: &[Value]) -> Result<Value, String> {
+    float_fn2("atan2", args, f32::atan2, f64::atan2)
+}
+
+fn pow(args: &[Value]) -> Result<Value, String> {
+    float_fn2("pow", args, f32::powf, f64::powf)
+}
+
+fn abs(args: &[Value]) -> Result<Value, String> {
+    let overflow = |i: &dyn std::fmt::Display, ty: &str| {
+        format!(
+            "Error[E419]: Integer overflow: abs({}) does not fit in {}",
+            i, ty
+        )
+    };
+    match args {
+        [Value::Int(i)] => i
+            .checked_abs()
+            .map(Value::Int)
+            .ok_or_else(|| overflow(i, "i32")),
+        [Value::Int64(i)] => i
+            .checked_abs()
+            .map(Value::Int64)
+            .ok_or_else(|| overflow(i, "i64")),
+        [Value::Float(f)] => Ok(Value::Float(f.abs())),
+        [Value::Float64(f)] => Ok(Value::Float64(f.abs())),
+        [Value::Vector2 { x, y }] => Ok(Value::Vector2 {
+            x: x.abs(),
+            y: y.abs(),
+        }),
+        _ => Err(expects("abs", "a number or Vector2")),
+    }
+}
+
+/// The smaller (`pick_min`) or larger of two numbers or, per component, two vectors
+fn min_max(name: &str, args: &[Value], pick_min: bool) -> Result<Value, String> {
+    let [a, b] = args else {
+        return Err(expects(name, "two numbers or two Vector2 values"));
+    };
+    let pick_f32 = |a: f32, b: f32| if pick_min { a.min(b) } else { a.max(b) };
+    if let (Value::Vector2 { x: ax, y: ay }, Value::Vector2 { x: bx, y: by }) = (a, b) {
+        return Ok(Value::Vector2 {
+            x: pick_f32(*ax, *bx),
+            y: pick_f32(*ay, *by),
+        });
+    }
+    Ok(match Operands::promote(a, b) {
+        Some(Operands::Int(a, b)) => Value::Int(if pick_min { a.min(b) } else { a.max(b) }),
+        Some(Operands::Int64(a, b)) => Value::Int64(if pick_min { a.min(b) } else { a.max(b) }),
+        Some(Operands::Float(a, b)) => Value::Float(pick_f32(a, b)),
+        Some(Operands::Float64(a, b)) => Value::Float64(if pick_min { a.min(b) } else { a.max(b) }),
+        None => return Err(expects(name, "two numbers or two Vector2 values")),
+    })
+}
+
+fn min(args: &[Value]) -> Result<Value, String> {
+    min_max("min", args, true)
+}
+
+fn max(args: &[Value]) -> Result<Value, String> {
+    min_max("max", args, false)
+}
+
+fn clamp(args: &[Value]) -> Result<Value, String> {
+    let [value, low, high] = args else {
+        return Err(expects("clamp", "a value and its lower and upper bounds"));
+    };
+    let raised = min_max("clamp", &[value.clone(), low.clone()], false)?;
+    min_max("clamp", &[raised, high.clone()], true)
+}
+
+fn lerp(args: &[Value]) -> Result<Value, String> {
+    let [from, to, weight] = args else {
+        return Err(expects("lerp", "two values and a weight"));
+    };
+    if let (Value::Vector2 { x: ax, y: ay }, Value::Vector2 { x: bx, y: by }) = (from, to) {
+        let t = weight
+            .to_float()
+            .ok_or_else(|| expects("lerp", "a number as the weight"))?;
+        return Ok(Value::Vector2 {
+            x: ax + (bx - ax) * t,
+            y: ay + (by - ay) * t,
+        });
+    }
+    let not_numbers = || expects("lerp", "two numbers or two Vector2 values and a weight");
+    let wide = [from, to, weight]
+        .iter()
+        .any(|v| matches!(v, Value::Int64(_) | Value::Float64(_)));
+    if wide {
+        let a = from.to_f64().ok_or_else(not_numbers)?;
+        let b = to.to_f64().ok_or_else(not_numbers)?;
+        let t = weight.to_f64().ok_or_else(not_numbers)?;
+        Ok(Value::Float64(a + (b - a) * t))
+    } else {
+        let a = from.to_float().ok_or_else(not_numbers)?;
+        let b = to.to_float().ok_or_else(not_numbers)?;
+        let t = weight.to_float().ok_or_else(not_numbers)?;
+        Ok(Value::Float(a + (b - a) * t))
+    }
+}
@@ -0,0 +1,110 @@
+//! Random number built-ins: `randi`, `randf`, `randi_range`, `randf_range` and
+//! `seed`.
+//!
+//! Each [`Env`] owns its own generator, so two scripts never share a sequence
+//! and the same seed always replays the same numbers. The generator is
+//! SplitMix64: small, fast, and well distributed for any seed, including 0.
+//! It is not suitable for cryptography.
+
+use crate::{Env, Value};
+
+/// Random functions draw from the environment's generator
+pub(crate) type RandomFn = fn(&mut Rng, &[Value]) -> Result<Value, String>;
+
+/// Random functions by name
+const FUNCTIONS: &[(&str, RandomFn)] = &[
+    ("randi", randi),
+    ("randf", randf),
+    ("randi_range", randi_range),
+    ("randf_range", randf_range),
+    ("seed", seed),
+];
+
+/// Deterministic pseudo-random number generator (SplitMix64)
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`, from the top 24 bits so every value is exact in an `f32`
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform in `low..=high`
+    fn range(&mut self, low: i32, high: i32) -> i32 {
+        let span = (high as i64 - low as i64) as u64 + 1;
+        (low as i64 + (self.next_u64() % span) as i64) as i32
+    }
+}
+
+/// Register the random functions in a new [`Env`]
+pub(crate) fn register(env: &mut Env) {
+    for (name, func) in FUNCTIONS {
+        env.random_fns.insert(name.to_string(), *func);
+    }
+}
+
+fn expects(name: &str, what: &str) -> String {
+    format!("{}() expects {}", name, what)
+}
+
+/// A non-negative `i32`
+fn randi(rng: &mut Rng, args: &[Value]) -> Result<Value, String> {
+    if !args.is_empty() {
+        return Err(expects("randi", "no arguments"));
+    }
+    Ok(Value::Int((rng.next_u64() >> 33) as i32))
+}
+
+/// An `f32` in `[0, 1)`
+fn randf(rng: &mut Rng, args: &[Value]) -> Result<Value, String> {
+    if !args.is_empty() {
+        return Err(expects("randf", "no arguments"));
+    }
+    Ok(Value::Float(rng.next_f32()))
+}
+
+/// An `i32` between the bounds, both included, in either order
+fn randi_range(rng: &mut Rng, args: &[Value]) -> Result<Value, String> {
+    let [Value::Int(a), Value::Int(b)] = args else {
+        return Err(expects("randi_range", "two i32 bounds"));
+    };
+    Ok(Value::Int(rng.range(*a.min(b), *a.max(b))))
+}
+
+/// An `f32` from `from` toward `to`, never reaching `to`
+fn randf_range(rng: &mut Rng, args: &[Value]) -> Result<Value, String> {
+    let bounds = match args {
+        [a, b] => a.to_float().zip(b.to_float()),
+        _ => None,
+    };
+    let Some((from, to)) = bounds else {
+        return Err(expects("randf_range", "two number bounds"));
+    };
+    Ok(Value::Float(from + (to - from) * rng.next_f32()))
+}
+
+/// Restart the sequence from `n`
+fn seed(rng: &mut Rng, args: &[Value]) -> Result<Value, String> {
+    let n = match args {
+        [Value::Int(n)] => *n as i64,
+        [Value::Int64(n)] => *n,
+        _ => return Err(expects("seed", "an integer")),
+    };
+    *rng = Rng::new(n as u64);
+    Ok(Value::Nil)
+}
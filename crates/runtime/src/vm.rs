@@ -0,0 +1,781 @@
+//! Stack-based virtual machine that executes compiled [`Chunk`]s.
+//!
+//! All frames of one entry call share a single value stack: each frame's local
+//! slots (`self`, then parameters, then locals and temporaries) start at its
+//! `base` and its operands are pushed above them. Calls between script
+//! functions push a [`Frame`] instead of recursing on the native stack.
+
+use crate::bytecode::{Chunk, Op, Place, PlaceRoot, Step};
+use crate::error::{RuntimeError, StackFrame};
+use crate::{
+    Closure, Env, Value, binary_op, call_builtin_method, call_function, cast, checked_index,
+    coerce, construct_struct, dict_insert, dict_mutation, expect_int, field_mut, field_ref,
+    format_value, get_field, index_array, is_dict_mutation, keep_width, pattern_matches, set_field,
+    set_self_property, unary_op,
+};
+use ferrisscript_compiler::ast;
+use std::borrow::Cow;
+use std::rc::Rc;
+
+/// An active function call
+struct Frame {
+    chunk: Rc<Chunk>,
+    /// Index of the frame's first local slot in the value stack
+    base: usize,
+    /// Instruction to resume at in the caller
+    return_ip: usize,
+    /// For method calls on a place: the caller's place index (its index operands
+    /// sit just below `base`) and whether `self` is written back to it
+    receiver_place: Option<(u32, bool)>,
+}
+
+/// Call the function or method at `id` in the code table.
+///
+/// For methods, `receiver` is bound to `self`; its final value is returned
+/// alongside the function's return value.
+pub(crate) fn call(
+    env: &mut Env,
+    id: usize,
+    args: Vec<Value>,
+    receiver: Option<Value>,
+) -> Result<(Value, Option<Value>), RuntimeError> {
+    let chunk = Rc::clone(&env.code[id]);
+    check_arity(&chunk, args.len())?;
+
+    let mut stack = args;
+    if chunk.self_param.is_some() {
+        stack.insert(0, receiver.unwrap_or(Value::Nil));
+    }
+    run(chunk, stack, env)
+}
+
+/// Run a chunk that takes no arguments, such as the global initializers
+pub(crate) fn run_chunk(chunk: Chunk, env: &mut Env) -> Result<Value, RuntimeError> {
+    run(Rc::new(chunk), Vec::new(), env).map(|(ret, _)| ret)
+}
+
+fn check_arity(chunk: &Chunk, argc: usize) -> Result<(), String> {
+    if argc != chunk.arity {
+        return Err(format!(
+            "Error[E416]: Function {} expects {} arguments, got {}",
+            chunk.name, chunk.arity, argc
+        ));
+    }
+    Ok(())
+}
+
+fn pop(stack: &mut Vec<Value>) -> Value {
+    stack.pop().expect("VM operand stack underflow")
+}
+
+/// Pop the top `n` values, preserving their order
+fn pop_n(stack: &mut Vec<Value>, n: usize) -> Vec<Value> {
+    stack.split_off(stack.len() - n)
+}
+
+/// Run `entry` with its arguments (and receiver) already on `stack`
+fn run(
+    entry: Rc<Chunk>,
+    mut stack: Vec<Value>,
+    env: &mut Env,
+) -> Result<(Value, Option<Value>), RuntimeError> {
+    stack.resize(entry.num_locals.max(stack.len()), Value::Nil);
+    let mut frames = vec![Frame {
+        chunk: entry,
+        base: 0,
+        return_ip: 0,
+        receiver_place: None,
+    }];
+    let mut ip = 0;
+    execute(&mut frames, &mut ip, stack, env).map_err(|error| trace(error, &frames, ip))
+}
+
+/// Execute instructions until the entry frame returns. On error, `frames` and
+/// `ip` are left at the failing instruction.
+fn execute(
+    frames: &mut Vec<Frame>,
+    ip: &mut usize,
+    mut stack: Vec<Value>,
+    env: &mut Env,
+) -> Result<(Value, Option<Value>), RuntimeError> {
+    let mut chunk = Rc::clone(&frames[0].chunk);
+    let mut base = 0;
+    let mut budget = match env.instruction_limit() {
+        0 => u64::MAX,
+        limit => limit,
+    };
+
+    loop {
+        let op = chunk.code[*ip];
+        *ip += 1;
+        if budget == 0 {
+            return Err(format!(
+                "Error[E420]: Instruction limit exceeded: '{}' ran more than {} instructions",
+                frames[0].chunk.name,
+                env.instruction_limit()
+            )
+            .into());
+        }
+        budget -= 1;
+
+        match op {
+            Op::Constant(i) => stack.push(chunk.constants[i as usize].clone()),
+
+            Op::Pop => {
+                pop(&mut stack);
+            }
+
+            Op::LoadLocal(slot) => {
+                let value = stack[base + slot as usize].clone();
+                stack.push(value);
+            }
+
+            Op::StoreLocal(slot) => {
+                let value = pop(&mut stack);
+                stack[base + slot as usize] = value;
+            }
+
+            Op::SetLocal(slot) => {
+                let value = pop(&mut stack);
+                let local = &mut stack[base + slot as usize];
+                *local = keep_width(local, value);
+            }
+
+            Op::LoadGlobal(name) => {
+                let name = &chunk.strings[name as usize];
+                let value = env
+                    .get(name)
+                    .cloned()
+                    .ok_or_else(|| format!("Error[E425]: Undefined variable: {}", name))?;
+                stack.push(value);
+            }
+
+            Op::StoreGlobal(name) => {
+                let value = pop(&mut stack);
+                env.assign(&chunk.strings[name as usize], value)?;
+            }
+
+            Op::DefineGlobal { name, mutable } => {
+                let value = pop(&mut stack);
+                env.set_with_mutability(chunk.strings[name as usize].clone(), value, mutable);
+            }
+
+            Op::ReadPlace(place) => {
+                let place = &chunk.places[place as usize];
+                let indices = pop_n(&mut stack, place.index_count());
+                let value = read_place(place, indices, &stack[base..], env)?;
+                stack.push(value);
+            }
+
+            Op::StorePlace(place) => {
+                let value = pop(&mut stack);
+                let place = &chunk.places[place as usize];
+                let indices = pop_n(&mut stack, place.index_count());
+                store_place(place, indices, value, &mut stack[base..], env)?;
+            }
+
+            Op::PushPlace(place) => {
+                let value = pop(&mut stack);
+                let place = &chunk.places[place as usize];
+                let indices = pop_n(&mut stack, place.index_count());
+                match collection_place_mut(place, indices, "array", &mut stack[base..], env)? {
+                    Value::Array(items) => {
+                        let value = match items.last() {
+                            Some(last) => keep_width(last, value),
+                            None => value,
+                        };
+                        items.push(value);
+                    }
+                    other => {
+                        return Err(format!("push() expects an array, found {:?}", other).into());
+                    }
+                }
+                stack.push(Value::Nil);
+            }
+
+            Op::PopPlace(place) => {
+                let place = &chunk.places[place as usize];
+                let indices = pop_n(&mut stack, place.index_count());
+                let value =
+                    match collection_place_mut(place, indices, "array", &mut stack[base..], env)? {
+                        Value::Array(items) => items.pop().ok_or_else(|| {
+                            "Error[E401]: Index out of bounds: cannot pop from an empty array"
+                                .to_string()
+                        })?,
+                        other => {
+                            return Err(format!("pop() expects an array, found {:?}", other).into());
+                        }
+                    };
+                stack.push(value);
+            }
+
+            Op::GetField(field) => {
+                let object = pop(&mut stack);
+                stack.push(get_field(&object, &chunk.strings[field as usize], env)?);
+            }
+
+            Op::Index => {
+                let index = expect_int(pop(&mut stack), "Array index")?;
+                let array = pop(&mut stack);
+                stack.push(index_array(&array, index)?);
+            }
+
+            Op::Binary(op) => {
+                let right = pop(&mut stack);
+                let left = pop(&mut stack);
+                stack.push(binary_op(op, left, right)?);
+            }
+
+            Op::Unary(op) => {
+                let operand = pop(&mut stack);
+                stack.push(unary_op(op, operand)?);
+            }
+
+            Op::Coerce(width) => {
+                let value = pop(&mut stack);
+                stack.push(coerce(value, width));
+            }
+
+            Op::Cast(to) => {
+                let value = pop(&mut stack);
+                stack.push(cast(value, to)?);
+            }
+
+            Op::ExpectInt(what) => {
+                if !matches!(stack.last(), Some(Value::Int(_))) {
+                    expect_int(pop(&mut stack), what)?;
+                }
+            }
+
+            Op::MakeOk => {
+                let value = pop(&mut stack);
+                stack.push(Value::Result(Ok(Box::new(value))));
+            }
+
+            Op::MakeErr => {
+                let value = pop(&mut stack);
+                stack.push(Value::Result(Err(Box::new(value))));
+            }
+
+            Op::Unwrap => match pop(&mut stack) {
+                Value::Result(Ok(value) | Err(value)) => stack.push(*value),
+                other => stack.push(other),
+            },
+
+            Op::Try(target) => match pop(&mut stack) {
+                Value::Result(Ok(value)) => {
+                    stack.push(*value);
+                    *ip = target as usize;
+                }
+                err @ Value::Result(Err(_)) => stack.push(err),
+                other => {
+                    return Err(format!(
+                        "Error[E424]: Invalid ? operand: expected a Result, found {}",
+                        format_value(&other)
+                    )
+                    .into());
+                }
+            },
+
+            Op::MakeArray(n) => {
+                let items = pop_n(&mut stack, n as usize);
+                stack.push(Value::Array(items));
+            }
+
+            Op::MakeDict(n) => {
+                let values = pop_n(&mut stack, 2 * n as usize);
+                let mut entries = Vec::with_capacity(n as usize);
+                let mut values = values.into_iter();
+                while let (Some(key), Some(value)) = (values.next(), values.next()) {
+                    dict_insert(&mut entries, key, value);
+                }
+                stack.push(Value::Dict(entries));
+            }
+
+            Op::MakeTuple(n) => {
+                let items = pop_n(&mut stack, n as usize);
+                stack.push(Value::Tuple(items));
+            }
+
+            Op::MakeStruct(literal) => {
+                let (type_name, names) = &chunk.literals[literal as usize];
+                let values = pop_n(&mut stack, names.len());
+                let fields = names.iter().cloned().zip(values).collect();
+                stack.push(construct_struct(type_name, fields, env)?);
+            }
+
+            Op::Jump(target) => *ip = target as usize,
+
+            Op::JumpIfFalse(target) => {
+                if !pop(&mut stack).to_bool() {
+                    *ip = target as usize;
+                }
+            }
+
+            Op::IterArray(slot) => {
+                let slot = base + slot as usize;
+                match pop(&mut stack) {
+                    array @ (Value::Array(_) | Value::Dict(_)) => {
+                        stack[slot] = array;
+                        stack[slot + 1] = Value::Int(0);
+                    }
+                    other => return Err(format!("Cannot iterate over {:?}", other).into()),
+                }
+            }
+
+            Op::ForRange { counter, var, exit } => {
+                let counter = base + counter as usize;
+                match (&stack[counter], &stack[counter + 1]) {
+                    (Value::Int(i), Value::Int(end)) if i < end => {
+                        let i = *i;
+                        stack[base + var as usize] = Value::Int(i);
+                        stack[counter] = Value::Int(i + 1);
+                    }
+                    _ => *ip = exit as usize,
+                }
+            }
+
+            Op::ForArray { array, var, exit } => {
+                let array = base + array as usize;
+                let next = match (&stack[array], &stack[array + 1]) {
+                    (Value::Array(items), Value::Int(i)) => {
+                        items.get(*i as usize).cloned().map(|item| (item, *i))
+                    }
+                    (Value::Dict(entries), Value::Int(i)) => {
+                        entries.get(*i as usize).map(|(key, _)| (key.clone(), *i))
+                    }
+                    _ => None,
+                };
+                match next {
+                    Some((item, i)) => {
+                        stack[base + var as usize] = item;
+                        stack[array + 1] = Value::Int(i + 1);
+                    }
+                    None => *ip = exit as usize,
+                }
+            }
+
+            Op::ForEntry {
+                dict,
+                key,
+                value,
+                exit,
+            } => {
+                let dict = base + dict as usize;
+                let next = match (&stack[dict], &stack[dict + 1]) {
+                    (Value::Dict(entries), Value::Int(i)) => {
+                        entries.get(*i as usize).cloned().map(|entry| (entry, *i))
+                    }
+                    (Value::Array(_), _) => {
+                        return Err("Cannot iterate over (key, value) pairs of an array"
+                            .to_string()
+                            .into());
+                    }
+                    _ => None,
+                };
+                match next {
+                    Some(((k, v), i)) => {
+                        stack[base + key as usize] = k;
+                        stack[base + value as usize] = v;
+                        stack[dict + 1] = Value::Int(i + 1);
+                    }
+                    None => *ip = exit as usize,
+                }
+            }
+
+            Op::MatchArm {
+                scrutinee,
+                pattern,
+                next,
+            } => {
+                if !pattern_matches(
+                    &chunk.patterns[pattern as usize],
+                    &stack[base + scrutinee as usize],
+                ) {
+                    *ip = next as usize;
+                }
+            }
+
+            Op::NoMatch(slot) => {
+                return Err(format!(
+                    "No match arm matched value {}",
+                    format_value(&stack[base + slot as usize])
+                )
+                .into());
+            }
+
+            Op::Call { function, argc } => {
+                let callee = Rc::clone(&env.code[function as usize]);
+                check_arity(&callee, argc as usize)?;
+                base = stack.len() - argc as usize;
+                check_depth(frames, &callee, env)?;
+                frames.push(enter(callee, base, *ip, None, &mut stack));
+                chunk = Rc::clone(&frames[frames.len() - 1].chunk);
+                *ip = 0;
+            }
+
+            Op::CallNamed { name, argc } => {
+                let args = pop_n(&mut stack, argc as usize);
+                stack.push(call_function(&chunk.strings[name as usize], &args, env)?);
+            }
+
+            Op::CallValue(argc) => {
+                let callee_at = stack.len() - argc as usize - 1;
+                let function = match stack.remove(callee_at) {
+                    Value::Function(function) => function,
+                    other => {
+                        return Err(format!(
+                            "Error[E423]: Value is not callable: cannot call {}",
+                            format_value(&other)
+                        )
+                        .into());
+                    }
+                };
+                let callee = Rc::clone(&function.chunk);
+                check_arity(&callee, argc as usize)?;
+                base = callee_at;
+                // The closure's captured values follow its parameters
+                stack.extend(function.captures.iter().cloned());
+                check_depth(frames, &callee, env)?;
+                frames.push(enter(callee, base, *ip, None, &mut stack));
+                chunk = Rc::clone(&frames[frames.len() - 1].chunk);
+                *ip = 0;
+            }
+
+            Op::LoadFunction(id) => stack.push(Value::Function(Rc::new(Closure {
+                chunk: Rc::clone(&env.code[id as usize]),
+                captures: Vec::new(),
+            }))),
+
+            Op::MakeClosure { function, captures } => {
+                let captures = pop_n(&mut stack, captures as usize);
+                stack.push(Value::Function(Rc::new(Closure {
+                    chunk: Rc::clone(&chunk.functions[function as usize]),
+                    captures,
+                })));
+            }
+
+            Op::CallMethod {
+                method,
+                argc,
+                place,
+            } => {
+                let argc = argc as usize;
+                let receiver_at = stack.len() - argc - 1;
+                let method = &chunk.strings[method as usize];
+
+                let type_name = match &stack[receiver_at] {
+                    Value::Struct { type_name, .. } => type_name,
+                    Value::Dict(_) if is_dict_mutation(method) => {
+                        let args = pop_n(&mut stack, argc);
+                        let mut receiver = pop(&mut stack);
+                        let Value::Dict(entries) = &mut receiver else {
+                            unreachable!("receiver is a dictionary");
+                        };
+                        let result = dict_mutation(entries, method, &args)?;
+                        // Write the changed dictionary back to the variable it came from
+                        if let Some(place) = place {
+                            let place = &chunk.places[place as usize];
+                            let indices = pop_n(&mut stack, place.index_count());
+                            *collection_place_mut(
+                                place,
+                                indices,
+                                "dictionary",
+                                &mut stack[base..],
+                                env,
+                            )? = receiver;
+                        }
+                        stack.push(result);
+                        continue;
+                    }
+                    _ => {
+                        let args = pop_n(&mut stack, argc);
+                        let receiver = pop(&mut stack);
+                        if let Some(place) = place {
+                            let place = &chunk.places[place as usize];
+                            stack.truncate(stack.len() - place.index_count());
+                        }
+                        stack.push(call_builtin_method(&receiver, method, &args)?);
+                        continue;
+                    }
+                };
+                let id = env.method_id(type_name, method).ok_or_else(|| {
+                    format!("Error[E415]: Undefined method: {}.{}", type_name, method)
+                })?;
+                let callee = Rc::clone(&env.code[id]);
+                check_arity(&callee, argc)?;
+
+                // A `&mut self` method runs on a copy of the receiver which is written
+                // back to its place on return, so the place must be mutable
+                let write_back = callee.self_param == Some(ast::SelfParam::RefMut);
+                if write_back
+                    && let Some(place) = place
+                    && !root_is_mutable(&chunk.places[place as usize], env)
+                {
+                    return Err(format!(
+                        "Error[E405]: Cannot call '&mut self' method '{}' on immutable variable '{}'",
+                        method,
+                        chunk.places[place as usize].root_name()
+                    )
+                    .into());
+                }
+
+                // An associated function called on a value does not bind `self`
+                if callee.self_param.is_none() {
+                    stack.remove(receiver_at);
+                }
+                base = receiver_at;
+                let receiver_place = place.map(|place| (place, write_back));
+                check_depth(frames, &callee, env)?;
+                frames.push(enter(callee, base, *ip, receiver_place, &mut stack));
+                chunk = Rc::clone(&frames[frames.len() - 1].chunk);
+                *ip = 0;
+            }
+
+            Op::Fail(message) => return Err(chunk.strings[message as usize].clone().into()),
+
+            Op::Return => {
+                let ret = pop(&mut stack);
+                let frame = frames.pop().expect("VM frame stack underflow");
+                let self_value = frame
+                    .chunk
+                    .self_param
+                    .map(|_| std::mem::replace(&mut stack[frame.base], Value::Nil));
+                stack.truncate(frame.base);
+
+                let Some(caller) = frames.last() else {
+                    return Ok((ret, self_value));
+                };
+                chunk = Rc::clone(&caller.chunk);
+                base = caller.base;
+                *ip = frame.return_ip;
+
+                if let Some((place, write_back)) = frame.receiver_place {
+                    let place = &chunk.places[place as usize];
+                    let indices = pop_n(&mut stack, place.index_count());
+                    if write_back && let Some(self_value) = self_value {
+                        *place_mut(place, indices, &mut stack[base..], env)? = self_value;
+                    }
+                }
+                stack.push(ret);
+            }
+        }
+    }
+}
+
+/// Set up the frame for a call whose locals start at `base`
+fn enter(
+    chunk: Rc<Chunk>,
+    base: usize,
+    return_ip: usize,
+    receiver_place: Option<(u32, bool)>,
+    stack: &mut Vec<Value>,
+) -> Frame {
+    stack.resize(base + chunk.num_locals, Value::Nil);
+    Frame {
+        chunk,
+        base,
+        return_ip,
+        receiver_place,
+    }
+}
+
+/// Fail with E403 when calling `callee` would nest deeper than the configured limit
+fn check_depth(frames: &[Frame], callee: &Chunk, env: &Env) -> Result<(), String> {
+    if frames.len() < env.max_call_depth() {
+        return Ok(());
+    }
+    Err(format!(
+        "Error[E403]: Stack overflow: calling '{}' exceeds the maximum call depth of {}",
+        callee.name,
+        env.max_call_depth()
+    ))
+}
+
+/// Record where execution stopped: the location of the failing instruction and
+/// the script functions on the call stack
+fn trace(mut error: RuntimeError, frames: &[Frame], ip: usize) -> RuntimeError {
+    let mut at = ip;
+    for frame in frames.iter().rev() {
+        let span = at
+            .checked_sub(1)
+            .and_then(|i| frame.chunk.spans.get(i))
+            .copied()
+            .unwrap_or_else(ast::Span::unknown);
+        error.call_stack.push(StackFrame {
+            function: frame.chunk.name.clone(),
+            span,
+        });
+        at = frame.return_ip;
+    }
+    if error.span.is_unknown()
+        && let Some(innermost) = error.call_stack.first()
+    {
+        error.span = innermost.span;
+    }
+    error
+}
+
+fn root_is_mutable(place: &Place, env: &Env) -> bool {
+    match &place.root {
+        PlaceRoot::Local { mutable, .. } => *mutable,
+        PlaceRoot::Global(name) => env.is_mutable(name),
+    }
+}
+
+/// Read the value at a place, cloning only the value itself
+fn read_place(
+    place: &Place,
+    indices: Vec<Value>,
+    stack: &[Value],
+    env: &Env,
+) -> Result<Value, String> {
+    let root = match &place.root {
+        PlaceRoot::Local { slot, .. } => &stack[*slot as usize],
+        PlaceRoot::Global(name) => env
+            .get(name)
+            .ok_or_else(|| format!("Error[E425]: Undefined variable: {}", name))?,
+    };
+
+    let mut indices = indices.into_iter();
+    let mut current = Cow::Borrowed(root);
+    for step in &place.steps {
+        current = match (step, current) {
+            (Step::Index, Cow::Borrowed(Value::Array(items))) => {
+                let index = expect_int(indices.next().unwrap_or(Value::Nil), "Array index")?;
+                Cow::Borrowed(&items[checked_index(index, items.len())?])
+            }
+            (Step::Index, value) => {
+                let index = expect_int(indices.next().unwrap_or(Value::Nil), "Array index")?;
+                Cow::Owned(index_array(&value, index)?)
+            }
+            (Step::Field(field), Cow::Borrowed(value)) => match field_ref(value, field) {
+                Some(inner) => Cow::Borrowed(inner),
+                None => Cow::Owned(get_field(value, field, env)?),
+            },
+            (Step::Field(field), Cow::Owned(value)) => Cow::Owned(get_field(&value, field, env)?),
+        };
+    }
+    Ok(current.into_owned())
+}
+
+/// Assign to a place, checking that its root variable is mutable
+fn store_place(
+    place: &Place,
+    indices: Vec<Value>,
+    value: Value,
+    stack: &mut [Value],
+    env: &mut Env,
+) -> Result<(), String> {
+    // `self.property = v` and `self.property.field = v` on the Godot node
+    if let PlaceRoot::Global(name) = &place.root
+        && matches!(env.get(name), Some(Value::SelfObject))
+    {
+        match place.steps.as_slice() {
+            [Step::Field(property)] => return set_self_property(property, None, value, env),
+            [Step::Field(property), Step::Field(field)] => {
+                return set_self_property(property, Some(field), value, env);
+            }
+            _ => {}
+        }
+    }
+
+    let Some((last, parents)) = place.steps.split_last() else {
+        return Err("Error[E426]: Invalid assignment target".to_string());
+    };
+    if !root_is_mutable_checked(place, env)? {
+        return Err(match last {
+            Step::Field(_) => format!(
+                "Error[E405]: Cannot assign to field of immutable variable '{}'",
+                place.root_name()
+            ),
+            Step::Index => format!(
+                "Error[E400]: Cannot mutate immutable array '{}'",
+                place.root_name()
+            ),
+        });
+    }
+
+    let mut indices = indices.into_iter();
+    let target = walk_mut(root_mut(place, stack, env)?, parents, &mut indices)?;
+    match last {
+        Step::Field(field) => set_field(target, field, value),
+        Step::Index => {
+            let element = walk_mut(target, std::slice::from_ref(last), &mut indices)?;
+            *element = keep_width(element, value);
+            Ok(())
+        }
+    }
+}
+
+/// Resolve the place of an array for `push`/`pop`, or of a dictionary for
+/// `insert`/`remove`, checking that its root is mutable
+fn collection_place_mut<'a>(
+    place: &Place,
+    indices: Vec<Value>,
+    what: &str,
+    stack: &'a mut [Value],
+    env: &'a mut Env,
+) -> Result<&'a mut Value, String> {
+    if !root_is_mutable_checked(place, env)? {
+        return Err(format!(
+            "Error[E400]: Cannot mutate immutable {} '{}'",
+            what,
+            place.root_name()
+        ));
+    }
+    place_mut(place, indices, stack, env)
+}
+
+/// Like [`root_is_mutable`], but reports an undefined global root
+fn root_is_mutable_checked(place: &Place, env: &Env) -> Result<bool, String> {
+    if let PlaceRoot::Global(name) = &place.root
+        && env.get(name).is_none()
+    {
+        return Err(format!("Error[E425]: Undefined variable: {}", name));
+    }
+    Ok(root_is_mutable(place, env))
+}
+
+/// Resolve a place to a mutable reference. Callers check that the root is mutable.
+fn place_mut<'a>(
+    place: &Place,
+    indices: Vec<Value>,
+    stack: &'a mut [Value],
+    env: &'a mut Env,
+) -> Result<&'a mut Value, String> {
+    let root = root_mut(place, stack, env)?;
+    walk_mut(root, &place.steps, &mut indices.into_iter())
+}
+
+fn root_mut<'a>(
+    place: &Place,
+    stack: &'a mut [Value],
+    env: &'a mut Env,
+) -> Result<&'a mut Value, String> {
+    match &place.root {
+        PlaceRoot::Local { slot, .. } => Ok(&mut stack[*slot as usize]),
+        PlaceRoot::Global(name) => env
+            .get_mut(name)
+            .ok_or_else(|| format!("Error[E425]: Undefined variable: {}", name)),
+    }
+}
+
+fn walk_mut<'a>(
+    mut value: &'a mut Value,
+    steps: &[Step],
+    indices: &mut impl Iterator<Item = Value>,
+) -> Result<&'a mut Value, String> {
+    for step in steps {
+        value = match (step, value) {
+            (Step::Index, Value::Array(items)) => {
+                let index = expect_int(indices.next().unwrap_or(Value::Nil), "Array index")?;
+                let i = checked_index(index, items.len())?;
+                &mut items[i]
+            }
+            (Step::Index, other) => return Err(format!("Cannot index into {:?}", other)),
+            (Step::Field(field), value) => field_mut(value, field)?,
+        };
+    }
+    Ok(value)
+}
@@ -0,0 +1,112 @@
+//! Integration tests for scripts that `use` modules
+//!
+//! Modules are compiled into the script, so their functions run like the
+//! script's own; runtime errors inside them must still point at the module.
+
+use ferrisscript_compiler::compile_with_loader;
+use ferrisscript_compiler::module::MemoryLoader;
+use ferrisscript_runtime::{Env, Value, call_function, execute};
+
+const COMBAT: &str = r#"use "math.ferris";
+
+const CRIT: f32 = 1.5;
+
+enum Element { Fire, Ice }
+
+struct Hit { amount: i32, element: Element }
+
+impl Hit {
+    fn fire(amount: i32) -> Hit {
+        return Hit { amount: amount, element: Element.Fire };
+    }
+}
+
+fn damage(hit: Hit, armor: i32) -> i32 {
+    let scaled = match hit.element {
+        Element.Fire => hit.amount as f32 * CRIT,
+        Element.Ice => hit.amount as f32,
+    };
+    return math.max0(scaled as i32 - armor);
+}
+
+fn ratio(a: i32, b: i32) -> i32 {
+    return a / b;
+}
+"#;
+
+fn loader() -> MemoryLoader {
+    MemoryLoader::new()
+        .with_file("res://lib/combat.ferris", COMBAT)
+        .with_file(
+            "res://lib/math.ferris",
+            "fn max0(x: i32) -> i32 { if x < 0 { return 0; } return x; }",
+        )
+}
+
+fn load(source: &str) -> Env {
+    let program = compile_with_loader(source, "res://player.ferris", &loader())
+        .unwrap_or_else(|e| panic!("{}", e));
+    let mut env = Env::new();
+    execute(&program, &mut env).unwrap();
+    env
+}
+
+#[test]
+fn test_script_calls_module_items() {
+    let mut env = load(
+        r#"
+        use "lib/combat.ferris";
+
+        let mut hp: i32 = 100;
+
+        fn take_fire(amount: i32) -> i32 {
+            hp -= combat.damage(combat.Hit.fire(amount), 2);
+            return hp;
+        }
+
+        fn take_ice(amount: i32) -> i32 {
+            let hit = combat.Hit { amount: amount, element: combat.Element.Ice };
+            hp -= combat.damage(hit, 50);
+            return hp;
+        }
+    "#,
+    );
+    assert_eq!(
+        call_function("take_fire", &[Value::Int(10)], &mut env).unwrap(),
+        Value::Int(87)
+    );
+    // Armor larger than the hit leaves hp alone
+    assert_eq!(
+        call_function("take_ice", &[Value::Int(10)], &mut env).unwrap(),
+        Value::Int(87)
+    );
+    // Module functions can be called by their qualified names
+    assert_eq!(
+        call_function("math.max0", &[Value::Int(-3)], &mut env).unwrap(),
+        Value::Int(0)
+    );
+}
+
+#[test]
+fn test_runtime_error_in_module_names_the_file() {
+    let source =
+        "use \"lib/combat.ferris\";\n\nfn half() -> i32 {\n    return combat.ratio(1, 0);\n}\n";
+    let program = compile_with_loader(source, "res://player.ferris", &loader()).unwrap();
+    let mut env = Env::new();
+    execute(&program, &mut env).unwrap();
+
+    let err = call_function("half", &[], &mut env).unwrap_err();
+    let report = err.format_with_files(&program.files);
+    assert!(
+        report.contains("Division by zero at line 24, column 12 in res://lib/combat.ferris"),
+        "{}",
+        report
+    );
+    assert!(report.contains("24 |     return a / b;"), "{}", report);
+    assert!(
+        report.contains("at combat.ratio (res://lib/combat.ferris, line 24)"),
+        "{}",
+        report
+    );
+    assert!(report.contains("at half (line 4)"), "{}", report);
+}
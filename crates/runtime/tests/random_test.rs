@@ -0,0 +1,172 @@
+//! Integration tests for the random number built-ins
+//!
+//! Every `Env` has its own seedable generator, so the same seed must replay the
+//! same sequence regardless of what other environments do in between.
+
+use ferrisscript_compiler::compile;
+use ferrisscript_runtime::{Env, Value, call_function, execute};
+
+const SPAWNER: &str = r#"
+    fn lane() -> i32 {
+        return randi_range(0, 3);
+    }
+
+    fn jitter() -> f32 {
+        return randf_range(-8.0, 8.0);
+    }
+
+    fn chance() -> f32 {
+        return randf();
+    }
+
+    fn id() -> i32 {
+        return randi();
+    }
+
+    fn reseed(n: i64) {
+        seed(n);
+    }
+"#;
+
+fn load(source: &str) -> Env {
+    let program = compile(source).unwrap();
+    let mut env = Env::new();
+    execute(&program, &mut env).unwrap();
+    env
+}
+
+/// Draw a mixed sequence of values the way a spawner would
+fn draw(env: &mut Env) -> Vec<Value> {
+    let mut values = Vec::new();
+    for _ in 0..8 {
+        for name in ["lane", "jitter", "chance", "id"] {
+            values.push(call_function(name, &[], env).unwrap());
+        }
+    }
+    values
+}
+
+#[test]
+fn test_same_seed_replays_same_sequence() {
+    let mut a = load(SPAWNER);
+    let mut b = load(SPAWNER);
+    a.set_random_seed(1234);
+    b.set_random_seed(1234);
+
+    // Drawing from another environment must not disturb either sequence
+    let mut other = load(SPAWNER);
+    draw(&mut other);
+
+    assert_eq!(draw(&mut a), draw(&mut b));
+}
+
+#[test]
+fn test_new_envs_start_from_the_default_seed() {
+    let mut a = load(SPAWNER);
+    let mut b = load(SPAWNER);
+    assert_eq!(draw(&mut a), draw(&mut b));
+}
+
+#[test]
+fn test_script_seed_matches_host_seed() {
+    let mut host = load(SPAWNER);
+    host.set_random_seed(99);
+    let expected = draw(&mut host);
+
+    let mut script = load(SPAWNER);
+    draw(&mut script);
+    call_function("reseed", &[Value::Int64(99)], &mut script).unwrap();
+    assert_eq!(draw(&mut script), expected);
+}
+
+#[test]
+fn test_different_seeds_give_different_sequences() {
+    let mut a = load(SPAWNER);
+    let mut b = load(SPAWNER);
+    a.set_random_seed(1);
+    b.set_random_seed(2);
+    assert_ne!(draw(&mut a), draw(&mut b));
+}
+
+#[test]
+fn test_random_values_stay_in_range() {
+    let mut env = load(SPAWNER);
+    for _ in 0..500 {
+        match call_function("lane", &[], &mut env).unwrap() {
+            Value::Int(lane) => assert!((0..=3).contains(&lane), "lane {}", lane),
+            other => panic!("expected Int, got {:?}", other),
+        }
+        match call_function("jitter", &[], &mut env).unwrap() {
+            Value::Float(f) => assert!((-8.0..8.0).contains(&f), "jitter {}", f),
+            other => panic!("expected Float, got {:?}", other),
+        }
+        match call_function("chance", &[], &mut env).unwrap() {
+            Value::Float(f) => assert!((0.0..1.0).contains(&f), "chance {}", f),
+            other => panic!("expected Float, got {:?}", other),
+        }
+        match call_function("id", &[], &mut env).unwrap() {
+            Value::Int(id) => assert!(id >= 0, "id {}", id),
+            other => panic!("expected Int, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_randi_range_accepts_bounds_in_either_order() {
+    let mut env = load(
+        r#"
+        fn roll() -> i32 {
+            return randi_range(6, 1);
+        }
+    "#,
+    );
+    let mut seen = [false; 6];
+    for _ in 0..200 {
+        let Value::Int(n) = call_function("roll", &[], &mut env).unwrap() else {
+            panic!("expected Int");
+        };
+        assert!((1..=6).contains(&n), "roll {}", n);
+        seen[(n - 1) as usize] = true;
+    }
+    assert!(seen.iter().all(|&s| s), "{:?}", seen);
+}
+
+#[test]
+fn test_global_initializers_use_the_seeded_generator() {
+    let program = compile(
+        r#"
+        let start: f32 = randf();
+
+        fn get_start() -> f32 {
+            return start;
+        }
+    "#,
+    )
+    .unwrap();
+    let mut seeded = Env::new();
+    seeded.set_random_seed(5);
+    execute(&program, &mut seeded).unwrap();
+
+    let mut expected = load(SPAWNER);
+    expected.set_random_seed(5);
+    assert_eq!(
+        call_function("get_start", &[], &mut seeded).unwrap(),
+        call_function("chance", &[], &mut expected).unwrap()
+    );
+}
+
+#[test]
+fn test_script_function_shadows_random_builtin() {
+    let mut env = load(
+        r#"
+        fn randi() -> i32 {
+            return 4;
+        }
+
+        fn roll() -> i32 {
+            return randi();
+        }
+    "#,
+    );
+    assert_eq!(call_function("roll", &[], &mut env).unwrap(), Value::Int(4));
+}
@@ -33,6 +33,7 @@ func _ready():
 	
 	# Run all tests
 	test_basic_functionality()
+	test_variant_round_trip()
 	
 	# Summary
 	print("")
@@ -71,6 +72,30 @@ func test_basic_functionality():
 		assert_equal(typeof("test"), TYPE_STRING, "String type")
 	)
 
+func test_variant_round_trip():
+	"""Round-trip Variants through FerrisScript Values (variant_to_value / value_to_variant)"""
+	run_test("variant_round_trip", func():
+		var node = ClassDB.instantiate("FerrisScriptTestNode")
+		if node == null:
+			return "FAIL: FerrisScriptTestNode not registered"
+		var cases = [
+			42,
+			2.5,
+			true,
+			"sword",
+			Vector2(1.5, -2.0),
+			Color(0.25, 0.5, 0.75, 1.0),
+			[1, "two", [3.5, false]],
+			{"sword": 10, "bag": [1, 2], 3: {"nested": true}},
+		]
+		for value in cases:
+			var failure = assert_equal(node.round_trip_variant(value), value, "round trip %s" % [value])
+			if failure != null:
+				node.free()
+				return failure
+		node.free()
+	)
+
 # ============================================================================
 # Test Framework Helpers
 # ============================================================================